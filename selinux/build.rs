@@ -0,0 +1,30 @@
+use std::env;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/call_back/log.c");
+
+    // This crate is empty for non-Linux platforms, and for the Linux kernel.
+    let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
+    let target_env = env::var("CARGO_CFG_TARGET_ENV").unwrap_or_default();
+    if target_os != "linux" || target_env == "kernel" {
+        return;
+    }
+
+    // Symbols shared between C and Rust are suffixed by the crate version,
+    // so that multiple versions of this crate can be linked into one binary.
+    let version = env::var("CARGO_PKG_VERSION").unwrap_or_default();
+    let suffix: String = version
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    println!("cargo:rustc-env=SELINUX_RS_SYMBOL_SUFFIX={suffix}");
+
+    // Stable Rust does not allow defining variadic functions, so the log call
+    // back trampoline is implemented in C.
+    cc::Build::new()
+        .file("src/call_back/log.c")
+        .define("SELINUX_RS_SYMBOL_SUFFIX", Some(suffix.as_str()))
+        .warnings(true)
+        .compile(&format!("selinux_call_back_log_{suffix}"));
+}
@@ -5,18 +5,146 @@ use std::convert::TryFrom;
 use std::marker::PhantomData;
 use std::mem::MaybeUninit;
 use std::os::raw::{c_char, c_int, c_uint, c_void};
-use std::sync::Once;
-use std::{io, ptr};
+use std::ptr::NonNull;
+use std::sync::{Mutex, MutexGuard, Once, PoisonError};
+use std::{io, ptr, thread};
 
 use reference_counted_singleton::{RCSRef, RefCountedSingleton};
 
 use crate::errors::{Error, Result};
 use crate::utils::{ret_val_to_result, str_to_c_string};
-use crate::SecurityContext;
+use crate::{AccessDecision, SecurityClass, SecurityContext};
 
 /// Access vector cache.
-#[derive(Debug, PartialEq, Eq)]
-pub struct AccessVectorCache(Vec<selinux_sys::selinux_opt>);
+///
+/// The access vector cache can be used from multiple threads.
+/// Operations on it are serialized.
+///
+/// ⚠️ Operations hold a lock that is not reentrant, while `libselinux` might
+/// call the [`Log`](crate::call_back::Log) and
+/// [`Audit`](crate::call_back::Audit) call backs. Call backs must not use
+/// the access vector cache, otherwise a dead lock occurs.
+#[derive(Debug)]
+pub struct AccessVectorCache {
+    options: Vec<selinux_sys::selinux_opt>,
+    netlink_thread: Option<NetlinkThread>,
+}
+
+// SAFETY: Options are only compared, the pointers they hold are never
+// dereferenced. Calls into `libselinux` are serialized by `lock_libselinux()`,
+// including those made by the netlink thread.
+unsafe impl Send for AccessVectorCache {}
+unsafe impl Sync for AccessVectorCache {}
+
+impl PartialEq for AccessVectorCache {
+    fn eq(&self, other: &Self) -> bool {
+        self.options == other.options
+            && self.netlink_thread.is_some() == other.netlink_thread.is_some()
+    }
+}
+
+impl Eq for AccessVectorCache {}
+
+/// Configuration of the user space access vector cache.
+///
+/// See: [`AccessVectorCache::initialize_with_config`].
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct AvcConfig {
+    enforcing: Option<bool>,
+    netlink_thread: bool,
+}
+
+impl AvcConfig {
+    /// Create a new configuration, where the enforcing mode follows
+    /// the kernel, and no netlink thread is started.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Override the enforcing mode of the access vector cache,
+    /// instead of following the kernel.
+    ///
+    /// See: `AVC_OPT_SETENFORCE`.
+    #[must_use]
+    pub fn enforcing(mut self, enforcing: bool) -> Self {
+        self.enforcing = Some(enforcing);
+        self
+    }
+
+    /// Start, or not, a thread listening to netlink notifications from
+    /// the kernel, such as policy reloads and enforcing mode changes.
+    ///
+    /// The thread is a Rust [`std::thread`], serialized with the other
+    /// operations on the access vector cache. It is stopped and joined before
+    /// the access vector cache is destroyed.
+    #[must_use]
+    pub fn netlink_thread(mut self, netlink_thread: bool) -> Self {
+        self.netlink_thread = netlink_thread;
+        self
+    }
+
+    /// Return the enforcing mode override, if any.
+    #[must_use]
+    pub fn enforcing_override(&self) -> Option<bool> {
+        self.enforcing
+    }
+
+    /// Return `true` if a netlink thread is to be started.
+    #[must_use]
+    pub fn uses_netlink_thread(&self) -> bool {
+        self.netlink_thread
+    }
+}
+
+/// Statistics of the user space access vector cache.
+///
+/// See: [`AccessVectorCache::statistics`].
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct AvcStatistics {
+    /// Number of cache entry lookups.
+    pub entry_lookups: u32,
+    /// Number of cache entry lookups that found a matching entry.
+    pub entry_hits: u32,
+    /// Number of cache entry lookups that did not find a matching entry.
+    pub entry_misses: u32,
+    /// Number of cache entries discarded because they did not grant
+    /// the requested permissions.
+    pub entry_discards: u32,
+    /// Number of cache lookups.
+    pub cav_lookups: u32,
+    /// Number of cache lookups that found a matching entry.
+    pub cav_hits: u32,
+    /// Number of cache nodes probed during cache lookups.
+    pub cav_probes: u32,
+    /// Number of cache lookups that did not find a matching entry.
+    pub cav_misses: u32,
+}
+
+impl From<selinux_sys::avc_cache_stats> for AvcStatistics {
+    fn from(stats: selinux_sys::avc_cache_stats) -> Self {
+        Self {
+            entry_lookups: stats.entry_lookups,
+            entry_hits: stats.entry_hits,
+            entry_misses: stats.entry_misses,
+            entry_discards: stats.entry_discards,
+            cav_lookups: stats.cav_lookups,
+            cav_hits: stats.cav_hits,
+            cav_probes: stats.cav_probes,
+            cav_misses: stats.cav_misses,
+        }
+    }
+}
+
+/// Serializes calls into the access vector cache and the status page of
+/// `libselinux`, which do not synchronize themselves.
+static LIBSELINUX_LOCK: Mutex<()> = Mutex::new(());
+
+pub(crate) fn lock_libselinux() -> MutexGuard<'static, ()> {
+    LIBSELINUX_LOCK
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
 
 static AVC_INIT: Once = Once::new();
 static mut AVC: MaybeUninit<RefCountedSingleton<AccessVectorCache>> = MaybeUninit::uninit();
@@ -58,6 +186,43 @@ impl AccessVectorCache {
         options.sort_unstable();
         options.dedup();
 
+        Self::open(options, false)
+    }
+
+    /// Initialize the user space access vector cache, according to `config`.
+    ///
+    /// If `config` requires a netlink thread, then a Rust [`std::thread`]
+    /// processes the netlink notifications received by `libselinux`.
+    ///
+    /// Attempting to initialize the access vector cache while it is still
+    /// initialized succeeds only if the subsequent initialization uses the same
+    /// configuration as the previous, still in scope, one.
+    ///
+    /// See: `avc_open()`, `avc_netlink_acquire_fd()`.
+    #[doc(alias = "avc_open")]
+    #[doc(alias = "avc_netlink_acquire_fd")]
+    pub fn initialize_with_config(config: &AvcConfig) -> Result<RCSRef<'static, Self>> {
+        // A non-null value enables the enforcing mode.
+        let options = match config.enforcing {
+            None => Vec::new(),
+
+            Some(enforcing) => vec![selinux_sys::selinux_opt {
+                type_: selinux_sys::AVC_OPT_SETENFORCE,
+                value: if enforcing {
+                    NonNull::dangling().as_ptr()
+                } else {
+                    ptr::null()
+                },
+            }],
+        };
+
+        Self::open(options, config.netlink_thread)
+    }
+
+    fn open(
+        mut options: Vec<selinux_sys::selinux_opt>,
+        netlink_thread: bool,
+    ) -> Result<RCSRef<'static, Self>> {
         let count = c_uint::try_from(options.len())?;
         let options_ptr = if count == 0 {
             ptr::null_mut()
@@ -65,21 +230,53 @@ impl AccessVectorCache {
             options.as_mut_ptr()
         };
 
+        Self::initialize_once(options.clone(), netlink_thread, || {
+            let r = unsafe { selinux_sys::avc_open(options_ptr, count) };
+            ret_val_to_result("avc_open()", r)
+        })
+    }
+
+    fn initialize_once(
+        options: Vec<selinux_sys::selinux_opt>,
+        netlink_thread: bool,
+        initialize: impl FnOnce() -> Result<()>,
+    ) -> Result<RCSRef<'static, Self>> {
         let mut newly_initialized = false;
         let avc = get_or_init_access_vector_cache();
 
         let result = avc.get_or_init(|| {
-            if unsafe { selinux_sys::avc_open(options_ptr, count) } == -1_i32 {
-                Err(Error::last_io_error("avc_open()"))
+            crate::status::open_access_vector_cache(|| {
+                let _guard = lock_libselinux();
+                initialize()
+            })?;
+
+            let thread = if netlink_thread {
+                match NetlinkThread::start() {
+                    Ok(thread) => Some(thread),
+
+                    Err(err) => {
+                        destroy();
+                        return Err(err);
+                    }
+                }
             } else {
-                newly_initialized = true;
-                Ok(AccessVectorCache(options.clone())) // First initialization succeeded.
-            }
+                None
+            };
+            newly_initialized = true;
+
+            // First initialization succeeded.
+            Ok(AccessVectorCache {
+                options: options.clone(),
+                netlink_thread: thread,
+            })
         });
 
         match result {
             Ok(value) => {
-                if newly_initialized || value.0 == options {
+                if newly_initialized
+                    || (value.options == options
+                        && value.netlink_thread.is_some() == netlink_thread)
+                {
                     // Either:
                     // 1. First initialization succeeded, or
                     // 2. Initializing, while still initialized, using the same
@@ -87,7 +284,7 @@ impl AccessVectorCache {
                     Ok(value)
                 } else {
                     // Initializing, while still initialized, with a different
-                    // set of options, is an error.
+                    // set of options or configuration, is an error.
                     let err = io::ErrorKind::AlreadyExists.into();
                     Err(Error::from_io("AccessVectorCache::initialize()", err))
                 }
@@ -107,6 +304,7 @@ impl AccessVectorCache {
     /// See: `avc_reset()`.
     #[doc(alias = "avc_reset")]
     pub fn reset(&self) -> Result<()> {
+        let _guard = self.lock();
         ret_val_to_result("avc_reset()", unsafe { selinux_sys::avc_reset() })
     }
 
@@ -116,43 +314,69 @@ impl AccessVectorCache {
     /// See: `avc_cleanup()`.
     #[doc(alias = "avc_cleanup")]
     pub fn clean_up(&self) {
+        let _guard = self.lock();
         unsafe { selinux_sys::avc_cleanup() }
     }
 
+    /// Return statistics of the user space access vector cache.
+    ///
+    /// ⚠️ These counters are updated only if `libselinux` is built with
+    /// `AVC_CACHE_STATS` defined, which is not the case for most distributions.
+    /// Otherwise, all counters read zero.
+    ///
+    /// See: `avc_cache_stats()`.
+    #[doc(alias = "avc_cache_stats")]
+    #[must_use]
+    pub fn statistics(&self) -> AvcStatistics {
+        let _guard = self.lock();
+        let mut stats = MaybeUninit::<selinux_sys::avc_cache_stats>::uninit();
+        unsafe { selinux_sys::avc_cache_stats(stats.as_mut_ptr()) };
+        AvcStatistics::from(unsafe { stats.assume_init() })
+    }
+
+    /// Log statistics of the access vector cache hash table, through
+    /// the [`Log`](crate::call_back::Log) call back.
+    ///
+    /// See: `avc_av_stats()`.
+    #[doc(alias = "avc_av_stats")]
+    pub fn log_access_vector_statistics(&self) {
+        let _guard = self.lock();
+        unsafe { selinux_sys::avc_av_stats() }
+    }
+
+    /// Log statistics of the security identifier table, through
+    /// the [`Log`](crate::call_back::Log) call back.
+    ///
+    /// See: `avc_sid_stats()`.
+    #[doc(alias = "avc_sid_stats")]
+    pub fn log_security_id_statistics(&self) {
+        let _guard = self.lock();
+        unsafe { selinux_sys::avc_sid_stats() }
+    }
+
     /// Return a security identifier for the kernel initial security identifier
     /// specified by `security_identifier_name`.
     ///
     /// See: `avc_get_initial_sid()`.
     #[doc(alias = "avc_get_initial_sid")]
-    pub fn kernel_initial_security_id<'context>(
-        &'context self,
-        security_id_name: &str,
-        raw_format: bool,
-    ) -> Result<SecurityID<'context>> {
+    pub fn kernel_initial_security_id(&self, security_id_name: &str) -> Result<SecurityID<'_>> {
         let c_name = str_to_c_string(security_id_name)?;
+        let _guard = self.lock();
         let mut security_id: *mut selinux_sys::security_id = ptr::null_mut();
-        if unsafe { selinux_sys::avc_get_initial_sid(c_name.as_ptr(), &mut security_id) } == -1_i32
-        {
-            Err(Error::last_io_error("avc_get_initial_sid()"))
-        } else {
-            Ok(SecurityID {
-                security_id,
-                is_raw: raw_format,
-                _phantom_data: PhantomData,
-            })
-        }
+        let r = unsafe { selinux_sys::avc_get_initial_sid(c_name.as_ptr(), &mut security_id) };
+        SecurityID::from_result("avc_get_initial_sid()", r, security_id)
     }
 
     /// Return a security context for the given security identifier.
     ///
     /// See: `avc_sid_to_context()`.
     #[doc(alias = "avc_sid_to_context")]
-    pub fn security_context_from_security_id<'context>(
-        &'context self,
-        mut security_id: SecurityID,
-    ) -> Result<SecurityContext<'context>> {
-        let is_raw = security_id.is_raw_format();
-        let (proc, proc_name): (unsafe extern "C" fn(_, _) -> _, _) = if is_raw {
+    pub fn security_context_from_security_id(
+        &self,
+        security_id: SecurityID,
+        raw_format: bool,
+    ) -> Result<SecurityContext<'static>> {
+        let (proc, proc_name): (unsafe extern "C" fn(_, _) -> _, _) = if raw_format {
             let proc_name = "avc_sid_to_context_raw()";
             (selinux_sys::avc_sid_to_context_raw, proc_name)
         } else {
@@ -160,21 +384,21 @@ impl AccessVectorCache {
             (selinux_sys::avc_sid_to_context, proc_name)
         };
 
+        let _guard = self.lock();
         let mut context: *mut c_char = ptr::null_mut();
-        let r = unsafe { proc(security_id.as_mut_ptr(), &mut context) };
-        SecurityContext::from_result(proc_name, r, context, is_raw)
+        let r = unsafe { proc(security_id.as_ptr(), &mut context) };
+        SecurityContext::from_result(proc_name, r, context, raw_format)
     }
 
     /// Return a security identifier for the given security context.
     ///
     /// See: `avc_context_to_sid()`.
     #[doc(alias = "avc_context_to_sid")]
-    pub fn security_id_from_security_context<'context>(
-        &'context self,
-        context: SecurityContext,
-    ) -> Result<SecurityID<'context>> {
-        let is_raw = context.is_raw_format();
-        let (proc, proc_name): (unsafe extern "C" fn(_, _) -> _, _) = if is_raw {
+    pub fn security_id_from_security_context(
+        &self,
+        context: &SecurityContext,
+    ) -> Result<SecurityID<'_>> {
+        let (proc, proc_name): (unsafe extern "C" fn(_, _) -> _, _) = if context.is_raw_format() {
             let proc_name = "avc_context_to_sid_raw()";
             (selinux_sys::avc_context_to_sid_raw, proc_name)
         } else {
@@ -182,66 +406,352 @@ impl AccessVectorCache {
             (selinux_sys::avc_context_to_sid, proc_name)
         };
 
+        let _guard = self.lock();
+        let mut security_id: *mut selinux_sys::security_id = ptr::null_mut();
+        let r = unsafe { proc(context.as_ptr(), &mut security_id) };
+        SecurityID::from_result(proc_name, r, security_id)
+    }
+
+    /// Compute a security identifier to use for labeling a new object in
+    /// the security class `class`, created by `source` in `target`.
+    ///
+    /// See: `avc_compute_create()`.
+    #[doc(alias = "avc_compute_create")]
+    pub fn compute_create(
+        &self,
+        source: &SecurityID,
+        target: &SecurityID,
+        class: SecurityClass,
+    ) -> Result<SecurityID<'_>> {
+        let _guard = self.lock();
         let mut security_id: *mut selinux_sys::security_id = ptr::null_mut();
-        if unsafe { proc(context.as_ptr(), &mut security_id) } == -1_i32 {
-            Err(Error::last_io_error(proc_name))
+        let r = unsafe {
+            selinux_sys::avc_compute_create(
+                source.as_ptr(),
+                target.as_ptr(),
+                class.value(),
+                &mut security_id,
+            )
+        };
+        SecurityID::from_result("avc_compute_create()", r, security_id)
+    }
+
+    /// Compute a security identifier to use when labeling a polyinstantiated
+    /// object instance of the security class `class`, accessed by `source`,
+    /// where `target` is the polyinstantiated object.
+    ///
+    /// See: `avc_compute_member()`.
+    #[doc(alias = "avc_compute_member")]
+    pub fn compute_member(
+        &self,
+        source: &SecurityID,
+        target: &SecurityID,
+        class: SecurityClass,
+    ) -> Result<SecurityID<'_>> {
+        let _guard = self.lock();
+        let mut security_id: *mut selinux_sys::security_id = ptr::null_mut();
+        let r = unsafe {
+            selinux_sys::avc_compute_member(
+                source.as_ptr(),
+                target.as_ptr(),
+                class.value(),
+                &mut security_id,
+            )
+        };
+        SecurityID::from_result("avc_compute_member()", r, security_id)
+    }
+
+    /// Check whether the `requested` permissions are granted to `source` on
+    /// `target`, for the security class `class`.
+    ///
+    /// Return `Ok(false)` if any requested permission is denied.
+    /// Granted and denied permissions are audited according to the policy,
+    /// and `audit_data` is passed to the [`Audit`](crate::call_back::Audit)
+    /// call back.
+    ///
+    /// ⚠️ The access vector cache is locked while call backs run, so they must
+    /// not use it, otherwise a dead lock occurs.
+    ///
+    /// See: `avc_has_perm()`.
+    #[doc(alias = "avc_has_perm")]
+    #[allow(clippy::not_unsafe_ptr_arg_deref)]
+    pub fn has_permission(
+        &self,
+        source: &SecurityID,
+        target: &SecurityID,
+        class: SecurityClass,
+        requested: selinux_sys::access_vector_t,
+        audit_data: *mut c_void,
+    ) -> Result<bool> {
+        let _guard = self.lock();
+        let r = unsafe {
+            selinux_sys::avc_has_perm(
+                source.as_ptr(),
+                target.as_ptr(),
+                class.value(),
+                requested,
+                ptr::null_mut(),
+                audit_data,
+            )
+        };
+
+        permission_check_result("avc_has_perm()", r)
+    }
+
+    /// Check whether the `requested` permissions are granted to `source` on
+    /// `target`, for the security class `class`, without auditing.
+    ///
+    /// Return whether all requested permissions are granted, along with
+    /// the access decision, which can be passed to [`Self::audit`].
+    ///
+    /// See: `avc_has_perm_noaudit()`.
+    #[doc(alias = "avc_has_perm_noaudit")]
+    pub fn has_permission_no_audit(
+        &self,
+        source: &SecurityID,
+        target: &SecurityID,
+        class: SecurityClass,
+        requested: selinux_sys::access_vector_t,
+    ) -> Result<(bool, AccessDecision)> {
+        let mut decision = MaybeUninit::<selinux_sys::av_decision>::uninit();
+        let _guard = self.lock();
+        let r = unsafe {
+            selinux_sys::avc_has_perm_noaudit(
+                source.as_ptr(),
+                target.as_ptr(),
+                class.value(),
+                requested,
+                ptr::null_mut(),
+                decision.as_mut_ptr(),
+            )
+        };
+
+        let granted = permission_check_result("avc_has_perm_noaudit()", r)?;
+        let decision = unsafe { decision.assume_init() };
+        Ok((granted, AccessDecision::new(class, requested, &decision)))
+    }
+
+    /// Audit the granting or denial of the requested permissions,
+    /// according to the access `decision` previously computed by
+    /// [`Self::has_permission_no_audit`].
+    ///
+    /// `audit_data` is passed to the [`Audit`](crate::call_back::Audit)
+    /// call back.
+    ///
+    /// ⚠️ The access vector cache is locked while call backs run, so they must
+    /// not use it, otherwise a dead lock occurs.
+    ///
+    /// See: `avc_audit()`.
+    #[doc(alias = "avc_audit")]
+    #[allow(clippy::not_unsafe_ptr_arg_deref)]
+    pub fn audit(
+        &self,
+        source: &SecurityID,
+        target: &SecurityID,
+        decision: &AccessDecision,
+        granted: bool,
+        audit_data: *mut c_void,
+    ) {
+        let mut av_decision = selinux_sys::av_decision::from(decision);
+        let _guard = self.lock();
+        let result = if granted { 0_i32 } else { -1_i32 };
+        unsafe {
+            selinux_sys::avc_audit(
+                source.as_ptr(),
+                target.as_ptr(),
+                decision.class().value(),
+                decision.requested(),
+                &mut av_decision,
+                result,
+                audit_data,
+            )
+        }
+    }
+}
+
+impl AccessVectorCache {
+    fn lock(&self) -> MutexGuard<'static, ()> {
+        lock_libselinux()
+    }
+}
+
+fn permission_check_result(proc_name: &'static str, result: c_int) -> Result<bool> {
+    if result == -1_i32 {
+        let err = io::Error::last_os_error();
+        if err.raw_os_error() == Some(libc::EACCES) {
+            Ok(false)
         } else {
-            Ok(SecurityID {
-                security_id,
-                is_raw,
-                _phantom_data: PhantomData,
-            })
+            Err(Error::from_io(proc_name, err))
         }
+    } else {
+        Ok(true)
     }
 }
 
 impl Drop for AccessVectorCache {
+    /// See: `avc_destroy()`.
     fn drop(&mut self) {
-        unsafe { selinux_sys::avc_destroy() };
+        // The netlink thread might be processing a notification, so it is
+        // joined before the access vector cache is destroyed.
+        if let Some(thread) = self.netlink_thread.take() {
+            thread.stop();
+        }
+        destroy();
     }
 }
 
-/// SELinux security identifier.
+fn destroy() {
+    crate::status::destroy_access_vector_cache(|| {
+        let _guard = lock_libselinux();
+        unsafe { selinux_sys::avc_destroy() }
+    });
+}
+
+/// Thread processing the netlink notifications received by `libselinux`.
+///
+/// Instead of running the blocking loop provided by `libselinux`, which cannot
+/// be interrupted, the thread waits for netlink notifications and processes
+/// them without blocking, until it is asked to stop through an event file
+/// descriptor.
 #[derive(Debug)]
-pub struct SecurityID<'id> {
-    security_id: *mut selinux_sys::security_id,
-    is_raw: bool,
-    _phantom_data: PhantomData<&'id selinux_sys::security_id>,
+struct NetlinkThread {
+    handle: thread::JoinHandle<()>,
+    shutdown_fd: c_int,
 }
 
-impl<'id> SecurityID<'id> {
-    /// Return `true` if the security identifier is unspecified.
-    #[must_use]
-    pub fn is_unspecified(&self) -> bool {
-        self.security_id.is_null()
-    }
+impl NetlinkThread {
+    /// See: `avc_netlink_acquire_fd()`.
+    fn start() -> Result<Self> {
+        // From now on, the netlink socket is serviced by this crate.
+        let netlink_fd = {
+            let _guard = lock_libselinux();
+            unsafe { selinux_sys::avc_netlink_acquire_fd() }
+        };
+        if netlink_fd < 0_i32 {
+            return Err(Error::last_io_error("avc_netlink_acquire_fd()"));
+        }
 
-    /// Return `false` if security context translation must be performed.
-    #[must_use]
-    pub fn is_raw_format(&self) -> bool {
-        self.is_raw
+        let shutdown_fd = unsafe { libc::eventfd(0, libc::EFD_CLOEXEC) };
+        if shutdown_fd == -1_i32 {
+            let err = Error::last_io_error("eventfd()");
+            release_netlink_fd();
+            return Err(err);
+        }
+
+        let builder = thread::Builder::new().name("selinux-avc-netlink".into());
+        match builder.spawn(move || netlink_loop(netlink_fd, shutdown_fd)) {
+            Ok(handle) => Ok(Self {
+                handle,
+                shutdown_fd,
+            }),
+
+            Err(err) => {
+                unsafe { libc::close(shutdown_fd) };
+                release_netlink_fd();
+                Err(Error::from_io("std::thread::Builder::spawn()", err))
+            }
+        }
     }
 
-    /// Return the managed raw pointer to [`selinux_sys::security_id`].
-    #[must_use]
-    pub fn as_ptr(&self) -> *const selinux_sys::security_id {
-        self.security_id.cast()
+    /// See: `avc_netlink_release_fd()`.
+    fn stop(self) {
+        let value = 1_u64;
+        unsafe {
+            libc::write(
+                self.shutdown_fd,
+                ptr::addr_of!(value).cast(),
+                std::mem::size_of::<u64>(),
+            )
+        };
+        let _ignored = self.handle.join();
+
+        unsafe { libc::close(self.shutdown_fd) };
+        release_netlink_fd();
     }
+}
 
-    /// Return the managed raw pointer to [`selinux_sys::security_id`].
-    #[must_use]
-    pub fn as_mut_ptr(&mut self) -> *mut selinux_sys::security_id {
-        self.security_id
+fn release_netlink_fd() {
+    let _guard = lock_libselinux();
+    unsafe { selinux_sys::avc_netlink_release_fd() };
+}
+
+fn netlink_loop(netlink_fd: c_int, shutdown_fd: c_int) {
+    let mut poll_fds = [
+        libc::pollfd {
+            fd: netlink_fd,
+            events: libc::POLLIN,
+            revents: 0,
+        },
+        libc::pollfd {
+            fd: shutdown_fd,
+            events: libc::POLLIN,
+            revents: 0,
+        },
+    ];
+
+    loop {
+        let r = unsafe { libc::poll(poll_fds.as_mut_ptr(), 2, -1) };
+        if r == -1_i32 {
+            if io::Error::last_os_error().kind() == io::ErrorKind::Interrupted {
+                continue;
+            }
+            break;
+        }
+
+        if poll_fds[1].revents != 0 {
+            break;
+        }
+
+        let revents = poll_fds[0].revents;
+        if revents & libc::POLLIN != 0 {
+            // Process all pending notifications, without blocking.
+            let _guard = lock_libselinux();
+            unsafe { selinux_sys::avc_netlink_check_nb() };
+        } else if revents & (libc::POLLERR | libc::POLLHUP | libc::POLLNVAL) != 0 {
+            break;
+        }
     }
 }
 
-impl<'id> Default for SecurityID<'id> {
-    /// Return an unspecified security identifier.
-    fn default() -> Self {
-        Self {
-            security_id: ptr::null_mut(),
-            is_raw: false,
-            _phantom_data: PhantomData,
+/// SELinux security identifier.
+///
+/// Security identifiers are shared by the access vector cache, and remain valid
+/// as long as it is initialized. Two security identifiers are equal if, and
+/// only if, they refer to the same security context.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct SecurityID<'id> {
+    security_id: NonNull<selinux_sys::security_id>,
+    _phantom_data: PhantomData<&'id AccessVectorCache>,
+}
+
+// SAFETY: Security identifiers are never modified through this type, and
+// the access vector cache serializes their use.
+unsafe impl Send for SecurityID<'_> {}
+unsafe impl Sync for SecurityID<'_> {}
+
+impl<'id> SecurityID<'id> {
+    fn from_result(
+        proc_name: &'static str,
+        result: c_int,
+        security_id: *mut selinux_sys::security_id,
+    ) -> Result<Self> {
+        if result == -1_i32 {
+            return Err(Error::last_io_error(proc_name));
         }
+
+        match NonNull::new(security_id) {
+            Some(security_id) => Ok(Self {
+                security_id,
+                _phantom_data: PhantomData,
+            }),
+
+            None => Err(Error::from_io(proc_name, io::ErrorKind::InvalidData.into())),
+        }
+    }
+
+    /// Return the managed raw pointer to [`selinux_sys::security_id`].
+    #[must_use]
+    pub fn as_ptr(&self) -> *mut selinux_sys::security_id {
+        self.security_id.as_ptr()
     }
 }
@@ -5,17 +5,6 @@ use std::{io, ptr};
 use assert_matches::assert_matches;
 use serial_test::serial;
 
-#[test]
-fn security_id_default() {
-    let mut sid = super::SecurityID::default();
-    assert!(sid.is_unspecified());
-    assert!(!sid.is_raw_format());
-    assert!(sid.as_ptr().is_null());
-    assert!(sid.as_mut_ptr().is_null());
-
-    let _ignored = format!("{:?}", &sid);
-}
-
 #[serial]
 #[test]
 fn access_vector_cache_initialize() {
@@ -83,18 +72,26 @@ fn access_vector_cache_clean_up() {
 fn access_vector_cache_kernel_initial_security_id() {
     let options = &[(selinux_sys::AVC_OPT_SETENFORCE, ptr::null())];
     let avc = super::AccessVectorCache::initialize(options).unwrap();
-    match avc.kernel_initial_security_id("unlabeled", false) {
-        Ok(mut sid) => {
-            assert_eq!(sid.as_ptr(), sid.as_mut_ptr());
-            assert!(!sid.is_raw_format());
-            assert!(!sid.is_unspecified());
-
-            let mut context = avc.security_context_from_security_id(sid).unwrap();
-            assert!(!context.is_raw_format());
-            assert_eq!(context.as_ptr(), context.as_mut_ptr());
-            assert!(!context.as_bytes().is_empty());
-
-            let _ignored = avc.security_id_from_security_context(context).unwrap();
+    match avc.kernel_initial_security_id("unlabeled") {
+        Ok(sid) => {
+            assert!(!sid.as_ptr().is_null());
+            let _ignored = format!("{sid:?}");
+
+            for raw_format in [false, true] {
+                let mut context = avc
+                    .security_context_from_security_id(sid, raw_format)
+                    .unwrap();
+                assert_eq!(context.is_raw_format(), raw_format);
+                assert_eq!(context.as_ptr(), context.as_mut_ptr());
+                assert!(!context.as_bytes().is_empty());
+
+                let sid_copy = avc.security_id_from_security_context(&context).unwrap();
+                assert_eq!(sid_copy, sid);
+            }
+
+            let mut sids = std::collections::HashSet::new();
+            assert!(sids.insert(sid));
+            assert!(!sids.insert(sid));
         }
 
         Err(err) => {
@@ -104,26 +101,169 @@ fn access_vector_cache_kernel_initial_security_id() {
             }
         }
     }
+}
 
-    match avc.kernel_initial_security_id("unlabeled", true) {
-        Ok(mut sid) => {
-            assert_eq!(sid.as_ptr(), sid.as_mut_ptr());
-            assert!(sid.is_raw_format());
-            assert!(!sid.is_unspecified());
+#[serial]
+#[test]
+fn access_vector_cache_has_permission() {
+    let options = &[(selinux_sys::AVC_OPT_SETENFORCE, ptr::null())];
+    let avc = super::AccessVectorCache::initialize(options).unwrap();
 
-            let mut context = avc.security_context_from_security_id(sid).unwrap();
-            assert!(context.is_raw_format());
-            assert_eq!(context.as_ptr(), context.as_mut_ptr());
-            assert!(!context.as_bytes().is_empty());
+    let sid = avc.kernel_initial_security_id("kernel");
+    let class = crate::SecurityClass::from_name("process");
+    let (sid, class) = match (sid, class) {
+        (Ok(sid), Ok(class)) => (sid, class),
+        _ => return,
+    };
 
-            let _ignored = avc.security_id_from_security_context(context).unwrap();
-        }
+    let requested = class.access_vector_bit("signal").unwrap();
 
-        Err(err) => {
-            assert_matches!(err, crate::errors::Error::IO { .. });
-            if let crate::errors::Error::IO { source, .. } = err {
-                assert_eq!(source.kind(), io::ErrorKind::NotFound);
+    let granted = avc
+        .has_permission(&sid, &sid, class, requested, ptr::null_mut())
+        .unwrap();
+
+    let (granted_no_audit, decision) = avc
+        .has_permission_no_audit(&sid, &sid, class, requested)
+        .unwrap();
+    assert_eq!(granted, granted_no_audit);
+    assert_eq!(decision.class(), class);
+    assert_eq!(decision.requested(), requested);
+    if !decision.is_permissive_domain() {
+        assert_eq!(granted, decision.is_allowed(requested));
+    }
+
+    avc.audit(&sid, &sid, &decision, granted, ptr::null_mut());
+}
+
+#[serial]
+#[test]
+fn access_vector_cache_statistics() {
+    let options = &[(selinux_sys::AVC_OPT_SETENFORCE, ptr::null())];
+    let avc = super::AccessVectorCache::initialize(options).unwrap();
+
+    let stats = avc.statistics();
+    assert!(stats.entry_hits <= stats.entry_lookups);
+    assert!(stats.cav_hits <= stats.cav_lookups);
+    let _ignored = format!("{stats:?}");
+
+    avc.log_access_vector_statistics();
+    avc.log_security_id_statistics();
+}
+
+#[test]
+fn avc_config() {
+    let config = super::AvcConfig::new();
+    assert_eq!(config, super::AvcConfig::default());
+    assert_eq!(config.enforcing_override(), None);
+    assert!(!config.uses_netlink_thread());
+
+    let config = config.enforcing(false).netlink_thread(true);
+    assert_eq!(config.enforcing_override(), Some(false));
+    assert!(config.uses_netlink_thread());
+
+    let _ignored = format!("{config:?}");
+}
+
+#[test]
+fn access_vector_cache_is_send_and_sync() {
+    fn assert_send_and_sync<T: Send + Sync>() {}
+    assert_send_and_sync::<super::AccessVectorCache>();
+}
+
+#[serial]
+#[test]
+fn access_vector_cache_initialize_with_config() {
+    let config = super::AvcConfig::new().enforcing(true).netlink_thread(true);
+    if let Ok(avc) = super::AccessVectorCache::initialize_with_config(&config) {
+        avc.reset().unwrap();
+    }
+
+    let config = super::AvcConfig::new().enforcing(false);
+    if let Ok(avc0) = super::AccessVectorCache::initialize_with_config(&config) {
+        let options = &[(selinux_sys::AVC_OPT_SETENFORCE, ptr::null())];
+        let avc1 = super::AccessVectorCache::initialize(options).unwrap();
+        assert_eq!(avc0, avc1);
+
+        let config = super::AvcConfig::new().netlink_thread(true);
+        super::AccessVectorCache::initialize_with_config(&config).unwrap_err();
+    }
+
+    // The netlink thread is stopped when the access vector cache is destroyed,
+    // so it can be initialized again.
+    for _ in 0..2_u8 {
+        let config = super::AvcConfig::new().netlink_thread(true);
+        match super::AccessVectorCache::initialize_with_config(&config) {
+            Ok(avc) => {
+                std::thread::scope(|scope| {
+                    scope.spawn(|| avc.reset().unwrap());
+                });
+
+                let _ignored = avc.statistics();
+            }
+
+            Err(err) => {
+                assert_matches!(err, crate::errors::Error::IO { .. });
             }
         }
     }
 }
+
+#[serial]
+#[test]
+fn access_vector_cache_compute_create_and_member() {
+    let options = &[(selinux_sys::AVC_OPT_SETENFORCE, ptr::null())];
+    let avc = super::AccessVectorCache::initialize(options).unwrap();
+
+    let source = avc.kernel_initial_security_id("kernel");
+    let target = avc.kernel_initial_security_id("file");
+    let class = crate::SecurityClass::from_name("file");
+    let (source, target, class) = match (source, target, class) {
+        (Ok(source), Ok(target), Ok(class)) => (source, target, class),
+        _ => return,
+    };
+
+    let sid = avc.compute_create(&source, &target, class).unwrap();
+    let context = avc.security_context_from_security_id(sid, true).unwrap();
+    assert!(!context.as_bytes().is_empty());
+
+    let sid = avc.compute_member(&source, &target, class).unwrap();
+    let context = avc.security_context_from_security_id(sid, true).unwrap();
+    assert!(!context.as_bytes().is_empty());
+}
+
+#[serial]
+#[test]
+fn access_vector_cache_drop_while_notified() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    // Changing the enforcing mode requires privileges, without which this
+    // only drops the access vector cache while its netlink thread waits.
+    let initial_mode = crate::current_mode();
+    let other_mode = match initial_mode {
+        crate::SELinuxMode::Enforcing => crate::SELinuxMode::Permissive,
+        crate::SELinuxMode::Permissive => crate::SELinuxMode::Enforcing,
+        _ => return,
+    };
+
+    let done = AtomicBool::new(false);
+    std::thread::scope(|scope| {
+        scope.spawn(|| {
+            while !done.load(Ordering::Relaxed) {
+                if crate::set_current_mode(other_mode).is_err() {
+                    break;
+                }
+                let _ignored = crate::set_current_mode(initial_mode);
+            }
+        });
+
+        for _ in 0..20_u8 {
+            let config = super::AvcConfig::new().netlink_thread(true);
+            if let Ok(avc) = super::AccessVectorCache::initialize_with_config(&config) {
+                let _ignored = avc.reset();
+            }
+        }
+        done.store(true, Ordering::Relaxed);
+    });
+
+    assert_eq!(crate::current_mode(), initial_mode);
+}
@@ -0,0 +1,271 @@
+#[cfg(test)]
+mod tests;
+
+mod semanage;
+
+use std::ffi::CString;
+use std::os::raw::{c_char, c_int};
+use std::{io, ptr, slice};
+
+use crate::errors::{Error, Result};
+use crate::utils::*;
+
+/// SELinux boolean, with its current and pending values.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Boolean {
+    name: String,
+    value: bool,
+    pending_value: bool,
+}
+
+impl Boolean {
+    /// Return the name of this boolean.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Return the current value of this boolean, as it was when this instance
+    /// was created.
+    #[must_use]
+    pub fn value(&self) -> bool {
+        self.value
+    }
+
+    /// Return the pending value of this boolean, as it was when this instance
+    /// was created.
+    ///
+    /// The pending value becomes the current value when pending values
+    /// are committed.
+    #[must_use]
+    pub fn pending_value(&self) -> bool {
+        self.pending_value
+    }
+
+    /// Return `true` if the pending value of this boolean differs from its
+    /// current value.
+    #[must_use]
+    pub fn has_pending_change(&self) -> bool {
+        self.value != self.pending_value
+    }
+
+    /// Return the boolean identified by `name`.
+    ///
+    /// See: `security_get_boolean_active()`, `security_get_boolean_pending()`.
+    #[doc(alias = "security_get_boolean_active")]
+    #[doc(alias = "security_get_boolean_pending")]
+    pub fn get(name: &str) -> Result<Self> {
+        let c_name = str_to_c_string(name)?;
+
+        let value = get_value(
+            selinux_sys::security_get_boolean_active,
+            "security_get_boolean_active()",
+            &c_name,
+            name,
+        )?;
+
+        let pending_value = get_value(
+            selinux_sys::security_get_boolean_pending,
+            "security_get_boolean_pending()",
+            &c_name,
+            name,
+        )?;
+
+        Ok(Self {
+            name: name.into(),
+            value,
+            pending_value,
+        })
+    }
+
+    /// Return all booleans defined by the loaded policy.
+    ///
+    /// See: `security_get_boolean_names()`.
+    #[doc(alias = "security_get_boolean_names")]
+    pub fn all() -> Result<Vec<Self>> {
+        names()?.iter().map(|name| Self::get(name)).collect()
+    }
+}
+
+fn get_value(
+    proc: unsafe extern "C" fn(*const c_char) -> c_int,
+    proc_name: &'static str,
+    c_name: &CString,
+    name: &str,
+) -> Result<bool> {
+    match unsafe { proc(c_name.as_ptr()) } {
+        -1_i32 => Err(Error::from_io_name(
+            proc_name,
+            name,
+            io::Error::last_os_error(),
+        )),
+
+        0_i32 => Ok(false),
+
+        _ => Ok(true),
+    }
+}
+
+/// Return the names of all booleans defined by the loaded policy.
+///
+/// See: `security_get_boolean_names()`.
+#[doc(alias = "security_get_boolean_names")]
+pub fn names() -> Result<Vec<String>> {
+    let mut names_ptr: *mut *mut c_char = ptr::null_mut();
+    let mut count: c_int = 0;
+    let r = unsafe { selinux_sys::security_get_boolean_names(&mut names_ptr, &mut count) };
+    if r == -1_i32 {
+        return Err(Error::last_io_error("security_get_boolean_names()"));
+    }
+
+    let names_list = CAllocatedBlock::new(names_ptr);
+    let names = match &names_list {
+        Some(names_list) if count > 0 => unsafe {
+            slice::from_raw_parts(names_list.as_ptr(), count as usize)
+        },
+
+        _ => &[],
+    };
+
+    // Every name is owned by the list, so all of them must be freed,
+    // even if some of them could not be converted.
+    let names: Vec<_> = names.iter().map(|&ptr| CAllocatedBlock::new(ptr)).collect();
+
+    names
+        .iter()
+        .map(|name| match name {
+            Some(name) => name
+                .as_c_str()
+                .to_str()
+                .map(String::from)
+                .map_err(Into::into),
+
+            None => {
+                let err = io::ErrorKind::InvalidData.into();
+                Err(Error::from_io("security_get_boolean_names()", err))
+            }
+        })
+        .collect()
+}
+
+/// Return the name of the boolean identified by `name`, after resolving it
+/// as an alias according to the `booleans.subs` configuration file.
+///
+/// If `name` is not an alias, then it is returned unchanged.
+///
+/// See: `selinux_boolean_sub()`, [`crate::path::booleans_subs`].
+#[doc(alias = "selinux_boolean_sub")]
+pub fn resolve_alias(name: &str) -> Result<String> {
+    let c_name = str_to_c_string(name)?;
+    let name_ptr = unsafe { selinux_sys::selinux_boolean_sub(c_name.as_ptr()) };
+    match CAllocatedBlock::new(name_ptr) {
+        Some(resolved) => Ok(resolved.as_c_str().to_str()?.into()),
+
+        None => {
+            let err = io::Error::last_os_error();
+            Err(Error::from_io_name("selinux_boolean_sub()", name, err))
+        }
+    }
+}
+
+/// Set the pending value of the boolean identified by `name`.
+///
+/// The new value takes effect only after calling [`commit_pending_values`].
+///
+/// See: `security_set_boolean()`.
+#[doc(alias = "security_set_boolean")]
+pub fn set_pending_value(name: &str, value: bool) -> Result<()> {
+    let c_name = str_to_c_string(name)?;
+    let r = unsafe { selinux_sys::security_set_boolean(c_name.as_ptr(), c_int::from(value)) };
+    if r == -1_i32 {
+        let err = io::Error::last_os_error();
+        Err(Error::from_io_name("security_set_boolean()", name, err))
+    } else {
+        Ok(())
+    }
+}
+
+/// Commit all pending values of booleans, making them the current values.
+///
+/// See: `security_commit_booleans()`.
+#[doc(alias = "security_commit_booleans")]
+pub fn commit_pending_values() -> Result<()> {
+    let r = unsafe { selinux_sys::security_commit_booleans() };
+    ret_val_to_result("security_commit_booleans()", r)
+}
+
+/// Set the values of multiple booleans, then commit them.
+///
+/// If setting any boolean fails, then all previously set pending values are
+/// reverted.
+/// The new values are not saved in the policy store, see
+/// [`set_values_persistently`] for that.
+///
+/// See: `security_set_boolean_list()`.
+#[doc(alias = "security_set_boolean_list")]
+pub fn set_values<N>(values: &[(N, bool)]) -> Result<()>
+where
+    N: AsRef<str>,
+{
+    let c_names = values
+        .iter()
+        .map(|(name, _)| str_to_c_string(name.as_ref()))
+        .collect::<Result<Vec<CString>>>()?;
+
+    // `security_set_boolean_list()` declares boolean names as mutable pointers,
+    // but never modifies the strings they point to.
+    let mut list: Vec<selinux_sys::SELboolean> = c_names
+        .iter()
+        .zip(values)
+        .map(|(c_name, &(_, value))| selinux_sys::SELboolean {
+            name: c_name.as_ptr() as *mut c_char,
+            value: c_int::from(value),
+        })
+        .collect();
+
+    let list_ptr = if list.is_empty() {
+        ptr::null_mut()
+    } else {
+        list.as_mut_ptr()
+    };
+
+    // `libselinux` no longer saves values permanently, and fails if asked to.
+    let r = unsafe { selinux_sys::security_set_boolean_list(list.len(), list_ptr, 0) };
+    ret_val_to_result("security_set_boolean_list()", r)
+}
+
+/// Set the values of multiple booleans, commit them, and save them in
+/// the policy store, so that they persist across reboots.
+///
+/// Boolean names are resolved as aliases before being set.
+///
+/// This operation is transactional: it is performed in a single transaction
+/// on the policy store, through `libsemanage`, which is loaded at run time.
+/// If setting or saving any boolean fails, then no boolean is changed.
+/// This fails with `ENOSYS` if `libsemanage` is not installed.
+///
+/// See: `selinux_boolean_sub()`, `semanage_bool_modify_local()`,
+/// `semanage_bool_set_active()`, `semanage_commit()`.
+#[doc(alias = "selinux_boolean_sub")]
+#[doc(alias = "semanage_bool_modify_local")]
+#[doc(alias = "semanage_bool_set_active")]
+pub fn set_values_persistently<N>(values: &[(N, bool)]) -> Result<()>
+where
+    N: AsRef<str>,
+{
+    let values = values
+        .iter()
+        .map(|(name, value)| resolve_alias(name.as_ref()).map(|name| (name, *value)))
+        .collect::<Result<Vec<(String, bool)>>>()?;
+
+    // Fail early if any boolean is not defined by the loaded policy.
+    for (name, _) in &values {
+        Boolean::get(name)?;
+    }
+
+    let mut transaction = semanage::Transaction::begin()?;
+    for (name, value) in &values {
+        transaction.set_boolean(name, *value)?;
+    }
+    transaction.commit()
+}
@@ -0,0 +1,202 @@
+// Minimal access to `libsemanage`, which manages the SELinux policy store.
+//
+// `libsemanage` is loaded at run time, because it is not linked by
+// `selinux-sys`, and is not installed on every system.
+
+use std::os::raw::{c_char, c_int, c_void};
+use std::{io, mem, ptr};
+
+use once_cell::sync::OnceCell;
+
+use crate::errors::{Error, Result};
+use crate::utils::str_to_c_string;
+
+/// Addresses of the `libsemanage` functions used by this crate.
+#[derive(Debug)]
+struct LibSemanage {
+    handle_create: unsafe extern "C" fn() -> *mut c_void,
+    handle_destroy: unsafe extern "C" fn(sh: *mut c_void),
+    is_managed: unsafe extern "C" fn(sh: *mut c_void) -> c_int,
+    connect: unsafe extern "C" fn(sh: *mut c_void) -> c_int,
+    disconnect: unsafe extern "C" fn(sh: *mut c_void) -> c_int,
+    begin_transaction: unsafe extern "C" fn(sh: *mut c_void) -> c_int,
+    commit: unsafe extern "C" fn(sh: *mut c_void) -> c_int,
+    bool_key_create:
+        unsafe extern "C" fn(sh: *mut c_void, name: *const c_char, key: *mut *mut c_void) -> c_int,
+    bool_key_free: unsafe extern "C" fn(key: *mut c_void),
+    bool_create: unsafe extern "C" fn(sh: *mut c_void, boolean: *mut *mut c_void) -> c_int,
+    bool_free: unsafe extern "C" fn(boolean: *mut c_void),
+    bool_set_name:
+        unsafe extern "C" fn(sh: *mut c_void, boolean: *mut c_void, name: *const c_char) -> c_int,
+    bool_set_value: unsafe extern "C" fn(boolean: *mut c_void, value: c_int),
+    bool_modify_local:
+        unsafe extern "C" fn(sh: *mut c_void, key: *const c_void, data: *const c_void) -> c_int,
+    bool_set_active:
+        unsafe extern "C" fn(sh: *mut c_void, key: *const c_void, data: *const c_void) -> c_int,
+}
+
+static LIB_SEMANAGE: OnceCell<Option<LibSemanage>> = OnceCell::new();
+
+impl LibSemanage {
+    fn get() -> Result<&'static Self> {
+        LIB_SEMANAGE
+            .get_or_init(Self::load)
+            .as_ref()
+            .ok_or_else(|| {
+                let err = io::Error::from_raw_os_error(libc::ENOSYS);
+                Error::from_io("dlopen(libsemanage)", err)
+            })
+    }
+
+    fn load() -> Option<Self> {
+        let flags = libc::RTLD_NOW | libc::RTLD_LOCAL | libc::RTLD_NODELETE;
+        let lib_handle = ["libsemanage.so.2\0", "libsemanage.so\0"]
+            .iter()
+            .map(|lib_name| unsafe { libc::dlopen(lib_name.as_ptr().cast(), flags) })
+            .find(|lib_handle| !lib_handle.is_null())?;
+
+        let symbol = |name: &str| {
+            let f = unsafe { libc::dlsym(lib_handle, name.as_ptr().cast()) };
+            if f.is_null() {
+                None
+            } else {
+                Some(f)
+            }
+        };
+
+        let r = unsafe {
+            Self {
+                handle_create: mem::transmute(symbol("semanage_handle_create\0")?),
+                handle_destroy: mem::transmute(symbol("semanage_handle_destroy\0")?),
+                is_managed: mem::transmute(symbol("semanage_is_managed\0")?),
+                connect: mem::transmute(symbol("semanage_connect\0")?),
+                disconnect: mem::transmute(symbol("semanage_disconnect\0")?),
+                begin_transaction: mem::transmute(symbol("semanage_begin_transaction\0")?),
+                commit: mem::transmute(symbol("semanage_commit\0")?),
+                bool_key_create: mem::transmute(symbol("semanage_bool_key_create\0")?),
+                bool_key_free: mem::transmute(symbol("semanage_bool_key_free\0")?),
+                bool_create: mem::transmute(symbol("semanage_bool_create\0")?),
+                bool_free: mem::transmute(symbol("semanage_bool_free\0")?),
+                bool_set_name: mem::transmute(symbol("semanage_bool_set_name\0")?),
+                bool_set_value: mem::transmute(symbol("semanage_bool_set_value\0")?),
+                bool_modify_local: mem::transmute(symbol("semanage_bool_modify_local\0")?),
+                bool_set_active: mem::transmute(symbol("semanage_bool_set_active\0")?),
+            }
+        };
+        Error::clear_errno();
+        Some(r)
+    }
+}
+
+/// Transaction on the SELinux policy store.
+///
+/// Dropping the transaction without committing it discards all changes.
+#[derive(Debug)]
+pub(super) struct Transaction {
+    lib: &'static LibSemanage,
+    handle: ptr::NonNull<c_void>,
+    is_connected: bool,
+}
+
+impl Transaction {
+    /// Connect to the policy store, and begin a transaction.
+    ///
+    /// See: `semanage_connect()`, `semanage_begin_transaction()`.
+    pub(super) fn begin() -> Result<Self> {
+        let lib = LibSemanage::get()?;
+
+        let handle = ptr::NonNull::new(unsafe { (lib.handle_create)() })
+            .ok_or_else(|| Error::last_io_error("semanage_handle_create()"))?;
+
+        let mut transaction = Self {
+            lib,
+            handle,
+            is_connected: false,
+        };
+
+        match unsafe { (lib.is_managed)(transaction.handle.as_ptr()) } {
+            1_i32 => {}
+
+            0_i32 => {
+                let err = io::ErrorKind::NotFound.into();
+                return Err(Error::from_io("semanage_is_managed()", err));
+            }
+
+            _ => return Err(Error::last_io_error("semanage_is_managed()")),
+        }
+
+        transaction.check("semanage_connect()", lib.connect)?;
+        transaction.is_connected = true;
+        transaction.check("semanage_begin_transaction()", lib.begin_transaction)?;
+        Ok(transaction)
+    }
+
+    /// Set the value of a boolean, both in the local policy store
+    /// customizations, and in the running kernel.
+    ///
+    /// See: `semanage_bool_modify_local()`, `semanage_bool_set_active()`.
+    pub(super) fn set_boolean(&mut self, name: &str, value: bool) -> Result<()> {
+        let c_name = str_to_c_string(name)?;
+        let sh = self.handle.as_ptr();
+
+        let mut key: *mut c_void = ptr::null_mut();
+        if unsafe { (self.lib.bool_key_create)(sh, c_name.as_ptr(), &mut key) } < 0_i32 {
+            let err = io::Error::last_os_error();
+            return Err(Error::from_io_name("semanage_bool_key_create()", name, err));
+        }
+
+        let mut boolean: *mut c_void = ptr::null_mut();
+        let r = if unsafe { (self.lib.bool_create)(sh, &mut boolean) } < 0_i32 {
+            Err("semanage_bool_create()")
+        } else if unsafe { (self.lib.bool_set_name)(sh, boolean, c_name.as_ptr()) } < 0_i32 {
+            Err("semanage_bool_set_name()")
+        } else {
+            unsafe { (self.lib.bool_set_value)(boolean, c_int::from(value)) };
+
+            if unsafe { (self.lib.bool_modify_local)(sh, key, boolean) } < 0_i32 {
+                Err("semanage_bool_modify_local()")
+            } else if unsafe { (self.lib.bool_set_active)(sh, key, boolean) } < 0_i32 {
+                Err("semanage_bool_set_active()")
+            } else {
+                Ok(())
+            }
+        };
+        let err = io::Error::last_os_error();
+
+        if !boolean.is_null() {
+            unsafe { (self.lib.bool_free)(boolean) };
+        }
+        unsafe { (self.lib.bool_key_free)(key) };
+
+        r.map_err(|proc_name| Error::from_io_name(proc_name, name, err))
+    }
+
+    /// Commit the transaction.
+    ///
+    /// See: `semanage_commit()`.
+    pub(super) fn commit(self) -> Result<()> {
+        self.check("semanage_commit()", self.lib.commit)
+    }
+
+    fn check(
+        &self,
+        proc_name: &'static str,
+        proc: unsafe extern "C" fn(*mut c_void) -> c_int,
+    ) -> Result<()> {
+        if unsafe { proc(self.handle.as_ptr()) } < 0_i32 {
+            Err(Error::last_io_error(proc_name))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Drop for Transaction {
+    /// See: `semanage_disconnect()`, `semanage_handle_destroy()`.
+    fn drop(&mut self) {
+        if self.is_connected {
+            unsafe { (self.lib.disconnect)(self.handle.as_ptr()) };
+        }
+        unsafe { (self.lib.handle_destroy)(self.handle.as_ptr()) };
+    }
+}
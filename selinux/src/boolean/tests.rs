@@ -0,0 +1,112 @@
+#![cfg(all(test, target_os = "linux", not(target_env = "kernel")))]
+
+use assert_matches::assert_matches;
+
+#[test]
+fn names() {
+    match super::names() {
+        Ok(names) => assert!(names.iter().all(|name| !name.is_empty())),
+        Err(_err) => assert_eq!(crate::current_mode(), crate::SELinuxMode::NotRunning),
+    }
+}
+
+#[test]
+fn boolean_all() {
+    match super::Boolean::all() {
+        Ok(booleans) => {
+            for boolean in &booleans {
+                assert!(!boolean.name().is_empty());
+                let _ignored = format!("{boolean:?}");
+            }
+        }
+
+        Err(_err) => assert_eq!(crate::current_mode(), crate::SELinuxMode::NotRunning),
+    }
+}
+
+#[test]
+fn boolean_get() {
+    let err = super::Boolean::get("invalid boolean name").unwrap_err();
+    assert_matches!(err, crate::errors::Error::IO1Name { .. });
+
+    super::Boolean::get("abc\0xyz").unwrap_err();
+
+    if let Some(name) = super::names()
+        .ok()
+        .and_then(|names| names.into_iter().next())
+    {
+        let boolean = super::Boolean::get(&name).unwrap();
+        assert_eq!(boolean.name(), name);
+        assert_eq!(
+            boolean.has_pending_change(),
+            boolean.value() != boolean.pending_value()
+        );
+    }
+}
+
+#[test]
+fn set_pending_value() {
+    let err = super::set_pending_value("invalid boolean name", true).unwrap_err();
+    assert_matches!(err, crate::errors::Error::IO1Name { .. });
+}
+
+#[test]
+fn set_values() {
+    super::set_values(&[("invalid boolean name", true)]).unwrap_err();
+    super::set_values(&[("abc\0xyz", true)]).unwrap_err();
+
+    let boolean = match super::Boolean::all()
+        .ok()
+        .and_then(|b| b.into_iter().next())
+    {
+        Some(boolean) => boolean,
+        None => return,
+    };
+
+    // Set the current value, leaving the system unchanged.
+    match super::set_values(&[(boolean.name(), boolean.value())]) {
+        Ok(()) => {
+            let new_boolean = super::Boolean::get(boolean.name()).unwrap();
+            assert_eq!(new_boolean.value(), boolean.value());
+        }
+
+        // Setting booleans requires privileges.
+        Err(_err) => assert_ne!(unsafe { libc::geteuid() }, 0),
+    }
+}
+
+#[test]
+fn resolve_alias() {
+    super::resolve_alias("abc\0xyz").unwrap_err();
+
+    if let Ok(names) = super::names() {
+        for name in &names {
+            assert!(!super::resolve_alias(name).unwrap().is_empty());
+        }
+    }
+}
+
+#[test]
+fn set_values_persistently() {
+    super::set_values_persistently(&[("invalid boolean name", true)]).unwrap_err();
+    super::set_values_persistently(&[("abc\0xyz", true)]).unwrap_err();
+
+    let boolean = match super::Boolean::all()
+        .ok()
+        .and_then(|b| b.into_iter().next())
+    {
+        Some(boolean) => boolean,
+        None => return,
+    };
+
+    // Persist the current value, leaving the system unchanged.
+    match super::set_values_persistently(&[(boolean.name(), boolean.value())]) {
+        Ok(()) => {
+            let new_boolean = super::Boolean::get(boolean.name()).unwrap();
+            assert_eq!(new_boolean.value(), boolean.value());
+        }
+
+        // Modifying the policy store requires privileges.
+        Err(_err) => assert_ne!(unsafe { libc::geteuid() }, 0),
+    }
+}
@@ -0,0 +1,58 @@
+#include <stdarg.h>
+#include <stdio.h>
+#include <stdlib.h>
+
+/*
+ * Symbols are suffixed by the crate version, defined by `build.rs`, so that
+ * multiple versions of the crate can be linked into one binary.
+ */
+#define SELINUX_RS_CONCAT_(prefix, suffix) prefix##suffix
+#define SELINUX_RS_CONCAT(prefix, suffix) SELINUX_RS_CONCAT_(prefix, suffix)
+#define SELINUX_RS_SYMBOL(name) SELINUX_RS_CONCAT(name##_, SELINUX_RS_SYMBOL_SUFFIX)
+
+/* Implemented in Rust, by `selinux::call_back`. */
+extern void SELINUX_RS_SYMBOL(selinux_rs_call_back_log_message)(int type, const char *message);
+
+/*
+ * Log call back installed by `selinux::call_back::Log::set_handler()`.
+ *
+ * Formats the message, then forwards it to the Rust handler.
+ */
+int SELINUX_RS_SYMBOL(selinux_rs_call_back_log)(int type, const char *fmt, ...)
+{
+	char stack_buffer[512];
+	char *buffer = stack_buffer;
+	va_list args;
+	int len;
+
+	if (fmt == NULL)
+		return -1;
+
+	va_start(args, fmt);
+	len = vsnprintf(stack_buffer, sizeof(stack_buffer), fmt, args);
+	va_end(args);
+
+	if (len < 0)
+		return -1;
+
+	if ((size_t)len >= sizeof(stack_buffer)) {
+		buffer = malloc((size_t)len + 1);
+		if (buffer == NULL)
+			return -1;
+
+		va_start(args, fmt);
+		len = vsnprintf(buffer, (size_t)len + 1, fmt, args);
+		va_end(args);
+
+		if (len < 0) {
+			free(buffer);
+			return -1;
+		}
+	}
+
+	SELINUX_RS_SYMBOL(selinux_rs_call_back_log_message)(type, buffer);
+
+	if (buffer != stack_buffer)
+		free(buffer);
+	return 0;
+}
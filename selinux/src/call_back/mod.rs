@@ -1,7 +1,13 @@
 #[cfg(test)]
 mod tests;
 
+use std::ffi::CStr;
 use std::os::raw::{c_char, c_int, c_void};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Mutex, PoisonError, RwLock};
+use std::{ptr, slice};
+
+use crate::{SELinuxMode, SecurityClass};
 
 /// Call back for SELinux operations.
 pub trait CallBack {
@@ -21,6 +27,63 @@ pub trait CallBack {
     fn set_call_back(call_back: Option<Self::CallBackType>);
 }
 
+/// Call back that can be implemented by a safe Rust closure.
+///
+/// Setting a handler installs an internal call back function that invokes
+/// the handler, and clearing the handler restores the call back function that
+/// was set before the handler.
+///
+/// Panics inside handlers are caught and never cross the foreign function
+/// interface boundary.
+/// A handler must not set or clear the handler of the same call back,
+/// otherwise a dead lock occurs.
+/// A handler must not call any method of
+/// [`AccessVectorCache`](crate::avc::AccessVectorCache) either, because
+/// handlers might be called while the access vector cache is locked.
+pub trait CallBackHandler: CallBack {
+    /// Prototype of handler.
+    type Handler: ?Sized;
+
+    /// Set or clear the handler.
+    ///
+    /// See: `selinux_set_callback()`.
+    #[doc(alias = "selinux_set_callback")]
+    fn set_handler(handler: Option<Box<Self::Handler>>);
+}
+
+/// Call back that was set before a handler, if a handler is set.
+///
+/// `libselinux` calls its call backs without checking them for null, so
+/// clearing a handler must never leave a null call back behind.
+type SavedCallBack<T> = Mutex<Option<Option<T>>>;
+
+fn set_handler_call_back<C>(
+    saved: &SavedCallBack<C::CallBackType>,
+    is_handler_set: bool,
+    trampoline: C::CallBackType,
+) where
+    C: CallBack,
+    C::CallBackType: Copy,
+{
+    let mut saved = saved.lock().unwrap_or_else(PoisonError::into_inner);
+    if is_handler_set {
+        if saved.is_none() {
+            *saved = Some(C::get_call_back());
+        }
+        C::set_call_back(Some(trampoline));
+    } else if let Some(Some(previous)) = saved.take() {
+        C::set_call_back(Some(previous));
+    }
+    // Otherwise, either no handler was set, or no call back was set before it.
+    // In the latter case, the internal call back remains, and does nothing.
+}
+
+/// Return the call back that was set before a handler, if a handler is set.
+fn saved_call_back<T: Copy>(saved: &SavedCallBack<T>) -> Option<T> {
+    let saved = *saved.lock().unwrap_or_else(PoisonError::into_inner);
+    saved.flatten()
+}
+
 /// Call back used for logging.
 #[derive(Debug, Default)]
 #[non_exhaustive]
@@ -39,6 +102,99 @@ impl CallBack for Log {
     }
 }
 
+/// Handler of [`Log`] call back.
+///
+/// The handler receives the log type and the already formatted message.
+pub type LogHandler = dyn Fn(LogType, &str) + Send + Sync;
+
+static LOG_HANDLER: RwLock<Option<Box<LogHandler>>> = RwLock::new(None);
+
+static LOG_SAVED_CALL_BACK: SavedCallBack<<Log as CallBack>::CallBackType> = Mutex::new(None);
+
+extern "C" {
+    // Implemented in `log.c`, with a symbol name suffixed by the crate version.
+    #[link_name = concat!("selinux_rs_call_back_log_", env!("SELINUX_RS_SYMBOL_SUFFIX"))]
+    fn selinux_rs_call_back_log(type_: c_int, fmt: *const c_char, ...) -> c_int;
+}
+
+/// Called by `selinux_rs_call_back_log()`, implemented in `log.c`.
+#[export_name = concat!(
+    "selinux_rs_call_back_log_message_",
+    env!("SELINUX_RS_SYMBOL_SUFFIX")
+)]
+unsafe extern "C" fn selinux_rs_call_back_log_message(type_: c_int, message: *const c_char) {
+    if message.is_null() {
+        return;
+    }
+
+    let message = unsafe { CStr::from_ptr(message) }.to_string_lossy();
+    let message = message.trim_end_matches('\n');
+    let _ignored = panic::catch_unwind(AssertUnwindSafe(|| {
+        let handler = LOG_HANDLER.read().unwrap_or_else(PoisonError::into_inner);
+        if let Some(handler) = handler.as_ref() {
+            handler(LogType::from(type_), message);
+        }
+    }));
+}
+
+impl CallBackHandler for Log {
+    type Handler = LogHandler;
+
+    fn set_handler(handler: Option<Box<Self::Handler>>) {
+        let is_set = handler.is_some();
+        *LOG_HANDLER.write().unwrap_or_else(PoisonError::into_inner) = handler;
+        set_handler_call_back::<Self>(&LOG_SAVED_CALL_BACK, is_set, selinux_rs_call_back_log);
+    }
+}
+
+impl Log {
+    /// Set a handler that forwards log messages to the [`log`] crate.
+    ///
+    /// Messages of types [`LogType::Error`], [`LogType::Warning`] and
+    /// [`LogType::Info`] are logged with the corresponding levels, under
+    /// the `selinux` target.
+    /// Messages of other types are logged with the `Info` level, under
+    /// the targets `selinux::avc`, `selinux::policy_load` and
+    /// `selinux::set_enforce`.
+    ///
+    /// This requires the `log` feature.
+    #[cfg(feature = "log")]
+    pub fn set_log_crate_handler() {
+        use log::Level;
+
+        Self::set_handler(Some(Box::new(|log_type, message: &str| {
+            let (target, level) = match log_type {
+                LogType::Error => ("selinux", Level::Error),
+                LogType::Warning => ("selinux", Level::Warn),
+                LogType::Info | LogType::Other(_) => ("selinux", Level::Info),
+                LogType::AVC => ("selinux::avc", Level::Info),
+                LogType::PolicyLoad => ("selinux::policy_load", Level::Info),
+                LogType::SetEnforce => ("selinux::set_enforce", Level::Info),
+            };
+
+            log::log!(target: target, level, "{message}");
+        })));
+    }
+
+    /// Set a handler that forwards log messages to the [`tracing`] crate.
+    ///
+    /// Messages are recorded as events, with the same levels and targets as
+    /// [`Log::set_log_crate_handler`].
+    ///
+    /// This requires the `tracing` feature.
+    #[cfg(feature = "tracing")]
+    pub fn set_tracing_handler() {
+        Self::set_handler(Some(Box::new(|log_type, message: &str| match log_type {
+            LogType::Error => tracing::error!(target: "selinux", "{message}"),
+            LogType::Warning => tracing::warn!(target: "selinux", "{message}"),
+            LogType::Info | LogType::Other(_) => tracing::info!(target: "selinux", "{message}"),
+            LogType::AVC => tracing::info!(target: "selinux::avc", "{message}"),
+            LogType::PolicyLoad => tracing::info!(target: "selinux::policy_load", "{message}"),
+            LogType::SetEnforce => tracing::info!(target: "selinux::set_enforce", "{message}"),
+        })));
+    }
+}
+
 /// Call back used for supplemental auditing in AVC messages.
 #[derive(Debug, Default)]
 #[non_exhaustive]
@@ -62,6 +218,57 @@ impl CallBack for Audit {
     }
 }
 
+/// Handler of [`Audit`] call back.
+///
+/// The handler receives the audit data and the security class supplied to
+/// the access check, and returns the supplemental audit message.
+pub type AuditHandler = dyn Fn(*mut c_void, SecurityClass) -> String + Send + Sync;
+
+static AUDIT_HANDLER: RwLock<Option<Box<AuditHandler>>> = RwLock::new(None);
+
+static AUDIT_SAVED_CALL_BACK: SavedCallBack<<Audit as CallBack>::CallBackType> = Mutex::new(None);
+
+unsafe extern "C" fn audit_trampoline(
+    audit_data: *mut c_void,
+    security_class: selinux_sys::security_class_t,
+    message_buffer: *mut c_char,
+    message_buffer_size: usize,
+) -> c_int {
+    let message = panic::catch_unwind(AssertUnwindSafe(|| {
+        let handler = AUDIT_HANDLER.read().unwrap_or_else(PoisonError::into_inner);
+        handler
+            .as_ref()
+            .map(|handler| handler(audit_data, SecurityClass(security_class)))
+    }));
+
+    match message {
+        Ok(Some(message)) if !message_buffer.is_null() && message_buffer_size > 0 => {
+            let buffer =
+                unsafe { slice::from_raw_parts_mut(message_buffer.cast(), message_buffer_size) };
+            let size = message.len().min(message_buffer_size - 1);
+            buffer[..size].copy_from_slice(&message.as_bytes()[..size]);
+            buffer[size] = 0_u8;
+            0_i32
+        }
+
+        Ok(_) => 0_i32,
+
+        Err(_) => -1_i32,
+    }
+}
+
+impl CallBackHandler for Audit {
+    type Handler = AuditHandler;
+
+    fn set_handler(handler: Option<Box<Self::Handler>>) {
+        let is_set = handler.is_some();
+        *AUDIT_HANDLER
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = handler;
+        set_handler_call_back::<Self>(&AUDIT_SAVED_CALL_BACK, is_set, audit_trampoline);
+    }
+}
+
 /// Call back used for context validation.
 #[derive(Debug, Default)]
 #[non_exhaustive]
@@ -80,6 +287,79 @@ impl CallBack for ContextValidation {
     }
 }
 
+/// Handler of [`ContextValidation`] call back.
+///
+/// The handler receives the security context to validate, and returns `true`
+/// if it is valid.
+pub type ContextValidationHandler = dyn Fn(&CStr) -> bool + Send + Sync;
+
+static CONTEXT_VALIDATION_HANDLER: RwLock<Option<Box<ContextValidationHandler>>> =
+    RwLock::new(None);
+
+static CONTEXT_VALIDATION_SAVED_CALL_BACK: SavedCallBack<
+    <ContextValidation as CallBack>::CallBackType,
+> = Mutex::new(None);
+
+unsafe extern "C" fn context_validation_trampoline(context_ptr: *mut *mut c_char) -> c_int {
+    let context = if context_ptr.is_null() {
+        ptr::null()
+    } else {
+        unsafe { *context_ptr }
+    };
+
+    if context.is_null() {
+        return -1_i32;
+    }
+
+    let context = unsafe { CStr::from_ptr(context) };
+    let is_valid = panic::catch_unwind(AssertUnwindSafe(|| {
+        let handler = CONTEXT_VALIDATION_HANDLER
+            .read()
+            .unwrap_or_else(PoisonError::into_inner);
+        handler.as_ref().map(|handler| handler(context))
+    }));
+
+    match is_valid {
+        Ok(Some(true)) => 0_i32,
+
+        // Without a handler, defer to the call back that was set before it.
+        // Contexts are never accepted by default.
+        Ok(None) => match saved_call_back(&CONTEXT_VALIDATION_SAVED_CALL_BACK) {
+            Some(previous) => unsafe { previous(context_ptr) },
+            None => -1_i32,
+        },
+
+        Ok(Some(false)) | Err(_) => -1_i32,
+    }
+}
+
+impl CallBackHandler for ContextValidation {
+    type Handler = ContextValidationHandler;
+
+    fn set_handler(handler: Option<Box<Self::Handler>>) {
+        let is_set = handler.is_some();
+
+        // The handler is set before the trampoline is installed, and cleared
+        // after it is uninstalled, so that the trampoline does not have to
+        // validate contexts without a handler.
+        if is_set {
+            *CONTEXT_VALIDATION_HANDLER
+                .write()
+                .unwrap_or_else(PoisonError::into_inner) = handler;
+        }
+        set_handler_call_back::<Self>(
+            &CONTEXT_VALIDATION_SAVED_CALL_BACK,
+            is_set,
+            context_validation_trampoline,
+        );
+        if !is_set {
+            *CONTEXT_VALIDATION_HANDLER
+                .write()
+                .unwrap_or_else(PoisonError::into_inner) = None;
+        }
+    }
+}
+
 /// Call back invoked when the system enforcing state changes.
 #[derive(Debug, Default)]
 #[non_exhaustive]
@@ -99,6 +379,56 @@ impl CallBack for EnforcingChange {
     }
 }
 
+/// Handler of [`EnforcingChange`] call back.
+///
+/// The handler receives the new SELinux enforcing mode.
+pub type EnforcingChangeHandler = dyn Fn(SELinuxMode) + Send + Sync;
+
+static ENFORCING_CHANGE_HANDLER: RwLock<Option<Box<EnforcingChangeHandler>>> = RwLock::new(None);
+
+static ENFORCING_CHANGE_SAVED_CALL_BACK: SavedCallBack<
+    <EnforcingChange as CallBack>::CallBackType,
+> = Mutex::new(None);
+
+unsafe extern "C" fn enforcing_change_trampoline(enforcing: c_int) -> c_int {
+    let mode = if enforcing == 0_i32 {
+        SELinuxMode::Permissive
+    } else {
+        SELinuxMode::Enforcing
+    };
+
+    let r = panic::catch_unwind(AssertUnwindSafe(|| {
+        let handler = ENFORCING_CHANGE_HANDLER
+            .read()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let Some(handler) = handler.as_ref() {
+            handler(mode);
+        }
+    }));
+
+    if r.is_ok() {
+        0_i32
+    } else {
+        -1_i32
+    }
+}
+
+impl CallBackHandler for EnforcingChange {
+    type Handler = EnforcingChangeHandler;
+
+    fn set_handler(handler: Option<Box<Self::Handler>>) {
+        let is_set = handler.is_some();
+        *ENFORCING_CHANGE_HANDLER
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = handler;
+        set_handler_call_back::<Self>(
+            &ENFORCING_CHANGE_SAVED_CALL_BACK,
+            is_set,
+            enforcing_change_trampoline,
+        );
+    }
+}
+
 /// Call back invoked when the system security policy is reloaded.
 #[derive(Debug, Default)]
 #[non_exhaustive]
@@ -118,6 +448,91 @@ impl CallBack for SecurityPolicyReload {
     }
 }
 
+/// Handler of [`SecurityPolicyReload`] call back.
+///
+/// The handler receives the sequence number of the loaded policy.
+pub type SecurityPolicyReloadHandler = dyn Fn(u32) + Send + Sync;
+
+static SECURITY_POLICY_RELOAD_HANDLER: RwLock<Option<Box<SecurityPolicyReloadHandler>>> =
+    RwLock::new(None);
+
+static SECURITY_POLICY_RELOAD_SAVED_CALL_BACK: SavedCallBack<
+    <SecurityPolicyReload as CallBack>::CallBackType,
+> = Mutex::new(None);
+
+unsafe extern "C" fn security_policy_reload_trampoline(seqno: c_int) -> c_int {
+    let r = panic::catch_unwind(AssertUnwindSafe(|| {
+        let handler = SECURITY_POLICY_RELOAD_HANDLER
+            .read()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let Some(handler) = handler.as_ref() {
+            handler(seqno as u32);
+        }
+    }));
+
+    if r.is_ok() {
+        0_i32
+    } else {
+        -1_i32
+    }
+}
+
+impl CallBackHandler for SecurityPolicyReload {
+    type Handler = SecurityPolicyReloadHandler;
+
+    fn set_handler(handler: Option<Box<Self::Handler>>) {
+        let is_set = handler.is_some();
+        *SECURITY_POLICY_RELOAD_HANDLER
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = handler;
+        set_handler_call_back::<Self>(
+            &SECURITY_POLICY_RELOAD_SAVED_CALL_BACK,
+            is_set,
+            security_policy_reload_trampoline,
+        );
+    }
+}
+
+/// Type of log message.
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum LogType {
+    /// Error log entry.
+    Error,
+    /// Warning log entry.
+    Warning,
+    /// Informational log entry.
+    Info,
+    /// AVC log entry.
+    AVC,
+    /// Policy loaded.
+    PolicyLoad,
+    /// SELinux enforcing mode changed.
+    SetEnforce,
+    /// Unknown log entry type.
+    Other(c_int),
+}
+
+impl From<c_int> for LogType {
+    fn from(value: c_int) -> Self {
+        if value == log_type::ERROR as c_int {
+            Self::Error
+        } else if value == log_type::WARNING as c_int {
+            Self::Warning
+        } else if value == log_type::INFO as c_int {
+            Self::Info
+        } else if value == log_type::AVC as c_int {
+            Self::AVC
+        } else if value == log_type::POLICY_LOAD {
+            Self::PolicyLoad
+        } else if value == log_type::SET_ENFORCE {
+            Self::SetEnforce
+        } else {
+            Self::Other(value)
+        }
+    }
+}
+
 /// Log type argument indicating the type of message.
 pub mod log_type {
     use std::os::raw::c_int;
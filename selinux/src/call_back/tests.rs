@@ -1,8 +1,12 @@
 #![cfg(all(test, target_os = "linux", not(target_env = "kernel")))]
 
 use core::mem;
+use std::ffi::CStr;
 use std::fmt;
 use std::os::raw::{c_char, c_int, c_void};
+use std::sync::{Arc, Mutex};
+
+use serial_test::serial;
 
 fn template<T>(call_back: <T as super::CallBack>::CallBackType)
 where
@@ -28,6 +32,7 @@ where
     assert_eq!(T::get_call_back(), old_call_back);
 }
 
+#[serial]
 #[test]
 fn log() {
     // # Safety
@@ -40,26 +45,215 @@ fn log() {
     template::<super::Log>(unsafe { mem::transmute(libc::abort as unsafe extern "C" fn() -> !) });
 }
 
+#[serial]
 #[test]
 fn audit() {
     template::<super::Audit>(audit_call_back);
 }
 
+#[serial]
 #[test]
 fn context_validation() {
     template::<super::ContextValidation>(context_validation_call_back);
 }
 
+#[serial]
 #[test]
 fn enforcing_change() {
     template::<super::EnforcingChange>(enforcing_change_call_back);
 }
 
+#[serial]
 #[test]
 fn security_policy_reload() {
     template::<super::SecurityPolicyReload>(security_policy_reload_call_back);
 }
 
+fn handler_template<T>(handler: Box<<T as super::CallBackHandler>::Handler>)
+where
+    T: super::CallBackHandler,
+    <T as super::CallBack>::CallBackType: fmt::Debug + Eq + Copy,
+{
+    let old_call_back = T::get_call_back();
+
+    // Clearing a handler that was never set changes nothing.
+    T::set_handler(None);
+    assert_eq!(T::get_call_back(), old_call_back);
+
+    T::set_handler(Some(handler));
+    let call_back = T::get_call_back();
+    assert!(call_back.is_some());
+    assert_ne!(call_back, old_call_back);
+
+    // Clearing the handler restores the previous call back, instead of
+    // leaving a null call back that `libselinux` would call.
+    T::set_handler(None);
+    assert_eq!(T::get_call_back(), old_call_back);
+}
+
+#[serial]
+#[test]
+fn log_handler() {
+    handler_template::<super::Log>(Box::new(|_log_type, _message: &str| ()));
+
+    let messages = Arc::new(Mutex::new(Vec::new()));
+    let messages_clone = Arc::clone(&messages);
+    let old_call_back = <super::Log as super::CallBack>::get_call_back();
+
+    <super::Log as super::CallBackHandler>::set_handler(Some(Box::new(
+        move |log_type, message: &str| {
+            let mut messages = messages_clone.lock().unwrap();
+            messages.push((log_type, message.to_owned()));
+        },
+    )));
+
+    let long_text = "x".repeat(1000);
+    let c_long_text = format!("{long_text}\0");
+    let r = unsafe {
+        super::selinux_rs_call_back_log(
+            super::log_type::INFO as c_int,
+            "abc %d %s\n\0".as_ptr().cast(),
+            5_i32,
+            c_long_text.as_ptr(),
+        )
+    };
+    assert_eq!(r, 0_i32);
+
+    let r = unsafe { super::selinux_rs_call_back_log(42_i32, "xyz\0".as_ptr().cast()) };
+    assert_eq!(r, 0_i32);
+
+    <super::Log as super::CallBackHandler>::set_handler(None);
+    assert_eq!(
+        <super::Log as super::CallBack>::get_call_back(),
+        old_call_back
+    );
+
+    let messages = messages.lock().unwrap();
+    assert_eq!(messages.len(), 2);
+    assert_eq!(messages[0].0, super::LogType::Info);
+    assert_eq!(messages[0].1, format!("abc 5 {long_text}"));
+    assert_eq!(messages[1].0, super::LogType::Other(42_i32));
+    assert_eq!(messages[1].1, "xyz");
+}
+
+#[serial]
+#[test]
+fn audit_handler() {
+    handler_template::<super::Audit>(Box::new(|_audit_data, _class| String::new()));
+
+    <super::Audit as super::CallBackHandler>::set_handler(Some(Box::new(|_audit_data, _class| {
+        String::from("abcdef")
+    })));
+
+    let mut buffer = [0xff_u8; 4];
+    let r = unsafe {
+        super::audit_trampoline(
+            std::ptr::null_mut(),
+            1,
+            buffer.as_mut_ptr().cast(),
+            buffer.len(),
+        )
+    };
+    assert_eq!(r, 0_i32);
+    assert_eq!(&buffer, b"abc\0");
+
+    <super::Audit as super::CallBackHandler>::set_handler(None);
+}
+
+#[serial]
+#[test]
+fn context_validation_handler() {
+    handler_template::<super::ContextValidation>(Box::new(|_context: &CStr| true));
+
+    <super::ContextValidation as super::CallBackHandler>::set_handler(Some(Box::new(
+        |context: &CStr| context.to_bytes() == b"valid",
+    )));
+
+    let mut context: *mut c_char = "valid\0".as_ptr() as *mut c_char;
+    assert_eq!(
+        unsafe { super::context_validation_trampoline(&mut context) },
+        0_i32
+    );
+
+    let mut context: *mut c_char = "invalid\0".as_ptr() as *mut c_char;
+    assert_eq!(
+        unsafe { super::context_validation_trampoline(&mut context) },
+        -1_i32
+    );
+
+    <super::ContextValidation as super::CallBackHandler>::set_handler(None);
+
+    // Without a handler, contexts are not accepted by default.
+    let mut context: *mut c_char = "valid\0".as_ptr() as *mut c_char;
+    assert_eq!(
+        unsafe { super::context_validation_trampoline(&mut context) },
+        -1_i32
+    );
+}
+
+#[serial]
+#[test]
+fn enforcing_change_handler() {
+    handler_template::<super::EnforcingChange>(Box::new(|_mode| ()));
+}
+
+#[serial]
+#[test]
+fn security_policy_reload_handler() {
+    handler_template::<super::SecurityPolicyReload>(Box::new(|_seqno| ()));
+}
+
+#[cfg(feature = "log")]
+#[serial]
+#[test]
+fn log_crate_handler() {
+    let old_call_back = <super::Log as super::CallBack>::get_call_back();
+
+    super::Log::set_log_crate_handler();
+    assert!(<super::Log as super::CallBack>::get_call_back().is_some());
+
+    let r = unsafe { super::selinux_rs_call_back_log(0_i32, "xyz\0".as_ptr().cast()) };
+    assert_eq!(r, 0_i32);
+
+    <super::Log as super::CallBackHandler>::set_handler(None);
+    assert_eq!(
+        <super::Log as super::CallBack>::get_call_back(),
+        old_call_back
+    );
+}
+
+#[cfg(feature = "tracing")]
+#[serial]
+#[test]
+fn tracing_handler() {
+    let old_call_back = <super::Log as super::CallBack>::get_call_back();
+
+    super::Log::set_tracing_handler();
+    assert!(<super::Log as super::CallBack>::get_call_back().is_some());
+
+    let r = unsafe { super::selinux_rs_call_back_log(0_i32, "xyz\0".as_ptr().cast()) };
+    assert_eq!(r, 0_i32);
+
+    <super::Log as super::CallBackHandler>::set_handler(None);
+    assert_eq!(
+        <super::Log as super::CallBack>::get_call_back(),
+        old_call_back
+    );
+}
+
+#[test]
+fn log_type() {
+    use super::{log_type, LogType};
+
+    assert_eq!(LogType::from(log_type::ERROR as c_int), LogType::Error);
+    assert_eq!(LogType::from(log_type::WARNING as c_int), LogType::Warning);
+    assert_eq!(LogType::from(log_type::INFO as c_int), LogType::Info);
+    assert_eq!(LogType::from(log_type::AVC as c_int), LogType::AVC);
+    assert_eq!(LogType::from(log_type::POLICY_LOAD), LogType::PolicyLoad);
+    assert_eq!(LogType::from(log_type::SET_ENFORCE), LogType::SetEnforce);
+    assert_eq!(LogType::from(-1_i32), LogType::Other(-1_i32));
+}
+
 // Dummy call back functions, of the correct prototypes.
 
 unsafe extern "C" fn audit_call_back(
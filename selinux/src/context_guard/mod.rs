@@ -0,0 +1,196 @@
+#[cfg(test)]
+mod tests;
+
+use std::marker::PhantomData;
+
+use crate::errors::Result;
+use crate::SecurityContext;
+
+mod private {
+    use crate::errors::Result;
+    use crate::SecurityContext;
+
+    pub trait Sealed {
+        fn get() -> Result<Option<SecurityContext<'static>>>;
+
+        fn set(context: &SecurityContext) -> Result<()>;
+
+        fn reset() -> Result<()>;
+    }
+
+    impl Sealed for super::Exec {
+        fn get() -> Result<Option<SecurityContext<'static>>> {
+            SecurityContext::of_next_exec(true)
+        }
+
+        fn set(context: &SecurityContext) -> Result<()> {
+            context.set_for_next_exec()
+        }
+
+        fn reset() -> Result<()> {
+            SecurityContext::set_default_context_for_next_exec()
+        }
+    }
+
+    impl Sealed for super::FsCreate {
+        fn get() -> Result<Option<SecurityContext<'static>>> {
+            SecurityContext::of_new_file_system_objects(true)
+        }
+
+        fn set(context: &SecurityContext) -> Result<()> {
+            context.set_for_new_file_system_objects(context.is_raw_format())
+        }
+
+        fn reset() -> Result<()> {
+            SecurityContext::set_default_context_for_new_file_system_objects()
+        }
+    }
+
+    impl Sealed for super::KeyCreate {
+        fn get() -> Result<Option<SecurityContext<'static>>> {
+            SecurityContext::of_new_kernel_key_rings(true)
+        }
+
+        fn set(context: &SecurityContext) -> Result<()> {
+            context.set_for_new_kernel_key_rings(context.is_raw_format())
+        }
+
+        fn reset() -> Result<()> {
+            SecurityContext::set_default_context_for_new_kernel_key_rings()
+        }
+    }
+
+    impl Sealed for super::SockCreate {
+        fn get() -> Result<Option<SecurityContext<'static>>> {
+            SecurityContext::of_new_labeled_sockets(true)
+        }
+
+        fn set(context: &SecurityContext) -> Result<()> {
+            context.set_for_new_labeled_sockets(context.is_raw_format())
+        }
+
+        fn reset() -> Result<()> {
+            SecurityContext::set_default_context_for_new_labeled_sockets()
+        }
+    }
+}
+
+/// Per-thread process attribute holding a context for new operations.
+///
+/// This trait is sealed, and cannot be implemented outside of this crate.
+pub trait AttributeKind: private::Sealed {}
+
+/// Attribute holding the context used for the next `execve()` call.
+///
+/// See: `getexeccon()`, `setexeccon()`.
+#[doc(alias = "setexeccon")]
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct Exec;
+
+impl AttributeKind for Exec {}
+
+/// Attribute holding the context used for creating new file system objects.
+///
+/// See: `getfscreatecon()`, `setfscreatecon()`.
+#[doc(alias = "setfscreatecon")]
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct FsCreate;
+
+impl AttributeKind for FsCreate {}
+
+/// Attribute holding the context used for creating new kernel key rings.
+///
+/// See: `getkeycreatecon()`, `setkeycreatecon()`.
+#[doc(alias = "setkeycreatecon")]
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct KeyCreate;
+
+impl AttributeKind for KeyCreate {}
+
+/// Attribute holding the context used for creating new labeled network
+/// sockets.
+///
+/// See: `getsockcreatecon()`, `setsockcreatecon()`.
+#[doc(alias = "setsockcreatecon")]
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct SockCreate;
+
+impl AttributeKind for SockCreate {}
+
+/// Guard setting the context held by the attribute `A`, and restoring the
+/// previous context when dropped.
+///
+/// This guard cannot be sent to another thread, because the context is
+/// a per-thread attribute.
+#[derive(Debug)]
+pub struct ContextGuard<A: AttributeKind> {
+    previous: Option<SecurityContext<'static>>,
+    is_restored: bool,
+    // Process attributes are per-thread, so the guard must not leave its thread.
+    _marker: PhantomData<(A, *const ())>,
+}
+
+/// Guard setting the context used for the next `execve()` call, and
+/// restoring the previous context when dropped.
+pub type ExecGuard = ContextGuard<Exec>;
+
+/// Guard setting the context used for creating new file system objects, and
+/// restoring the previous context when dropped.
+pub type FsCreateGuard = ContextGuard<FsCreate>;
+
+/// Guard setting the context used for creating new kernel key rings, and
+/// restoring the previous context when dropped.
+pub type KeyCreateGuard = ContextGuard<KeyCreate>;
+
+/// Guard setting the context used for creating new labeled network sockets,
+/// and restoring the previous context when dropped.
+pub type SockCreateGuard = ContextGuard<SockCreate>;
+
+impl<A: AttributeKind> ContextGuard<A> {
+    /// Set the context held by the attribute, or reset it to the default
+    /// policy behavior if `context` is `None`.
+    pub fn new(context: Option<&SecurityContext>) -> Result<Self> {
+        let previous = A::get()?;
+        match context {
+            Some(context) => A::set(context)?,
+            None => A::reset()?,
+        }
+
+        Ok(Self {
+            previous,
+            is_restored: false,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Return the context that will be restored, if any.
+    #[must_use]
+    pub fn previous(&self) -> Option<&SecurityContext<'static>> {
+        self.previous.as_ref()
+    }
+
+    /// Restore the previous context, reporting any failure.
+    pub fn restore(mut self) -> Result<()> {
+        self.restore_previous()
+    }
+
+    fn restore_previous(&mut self) -> Result<()> {
+        self.is_restored = true;
+        match &self.previous {
+            Some(previous) => A::set(previous),
+            None => A::reset(),
+        }
+    }
+}
+
+impl<A: AttributeKind> Drop for ContextGuard<A> {
+    fn drop(&mut self) {
+        if !self.is_restored {
+            let _ignored = self.restore_previous();
+        }
+    }
+}
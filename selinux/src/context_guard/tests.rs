@@ -0,0 +1,81 @@
+#![cfg(all(test, target_os = "linux", not(target_env = "kernel")))]
+
+fn as_bytes(context: Option<&crate::SecurityContext>) -> Option<Vec<u8>> {
+    context.map(|context| context.as_bytes().to_vec())
+}
+
+#[test]
+fn exec_guard() {
+    let old_context = crate::SecurityContext::of_next_exec(true).unwrap();
+    let context = crate::SecurityContext::current(true).unwrap();
+
+    let guard = super::ExecGuard::new(Some(&context)).unwrap();
+    assert_eq!(as_bytes(guard.previous()), as_bytes(old_context.as_ref()));
+    let new_context = crate::SecurityContext::of_next_exec(true).unwrap();
+    assert_eq!(as_bytes(new_context.as_ref()), as_bytes(Some(&context)));
+    let _ignored = format!("{:?}", &guard);
+    drop(guard);
+
+    let restored = crate::SecurityContext::of_next_exec(true).unwrap();
+    assert_eq!(as_bytes(restored.as_ref()), as_bytes(old_context.as_ref()));
+
+    let guard = super::ExecGuard::new(None).unwrap();
+    assert!(crate::SecurityContext::of_next_exec(true)
+        .unwrap()
+        .is_none());
+    guard.restore().unwrap();
+}
+
+#[test]
+fn fs_create_guard() {
+    let old_context = crate::SecurityContext::of_new_file_system_objects(true).unwrap();
+    let context = crate::SecurityContext::current(false).unwrap();
+
+    let guard = super::FsCreateGuard::new(Some(&context)).unwrap();
+    assert!(crate::SecurityContext::of_new_file_system_objects(true)
+        .unwrap()
+        .is_some());
+    guard.restore().unwrap();
+
+    let restored = crate::SecurityContext::of_new_file_system_objects(true).unwrap();
+    assert_eq!(as_bytes(restored.as_ref()), as_bytes(old_context.as_ref()));
+}
+
+#[test]
+fn key_create_guard() {
+    let old_context = crate::SecurityContext::of_new_kernel_key_rings(true).unwrap();
+    let context = crate::SecurityContext::current(true).unwrap();
+
+    {
+        let _guard = super::KeyCreateGuard::new(Some(&context)).unwrap();
+        let new_context = crate::SecurityContext::of_new_kernel_key_rings(true).unwrap();
+        assert_eq!(as_bytes(new_context.as_ref()), as_bytes(Some(&context)));
+    }
+
+    let restored = crate::SecurityContext::of_new_kernel_key_rings(true).unwrap();
+    assert_eq!(as_bytes(restored.as_ref()), as_bytes(old_context.as_ref()));
+}
+
+#[test]
+fn sock_create_guard() {
+    let old_context = crate::SecurityContext::of_new_labeled_sockets(true).unwrap();
+    let context = crate::SecurityContext::current(true).unwrap();
+
+    set_sock_create_and_fail(&context).unwrap_err();
+
+    let restored = crate::SecurityContext::of_new_labeled_sockets(true).unwrap();
+    assert_eq!(as_bytes(restored.as_ref()), as_bytes(old_context.as_ref()));
+}
+
+fn set_sock_create_and_fail(context: &crate::SecurityContext) -> crate::errors::Result<()> {
+    let _guard = super::SockCreateGuard::new(Some(context))?;
+    let new_context = crate::SecurityContext::of_new_labeled_sockets(true)?;
+    assert_eq!(as_bytes(new_context.as_ref()), as_bytes(Some(context)));
+
+    // An early return restores the previous context.
+    let err = std::io::ErrorKind::Other.into();
+    Err(crate::errors::Error::from_io(
+        "set_sock_create_and_fail()",
+        err,
+    ))
+}
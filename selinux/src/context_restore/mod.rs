@@ -13,6 +13,7 @@ use crate::utils::*;
 
 bitflags! {
     /// Flags controlling relabeling operations.
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct RestoreFlags: c_uint {
         /// Force the checking of labels even if the stored SHA1 digest matches
         /// the specfile entries SHA1 digest.
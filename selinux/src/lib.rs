@@ -58,18 +58,28 @@ mod tests;
 
 /// Access Vector Cache.
 pub mod avc;
+/// SELinux booleans.
+pub mod boolean;
 /// SELinux call backs.
 pub mod call_back;
+/// Scoped per-thread contexts for new processes and objects.
+pub mod context_guard;
 /// Restore file(s) default SELinux security contexts.
 pub mod context_restore;
 /// Errors.
 pub mod errors;
 /// Labeling files.
 pub mod label;
+/// Multi-level security levels and ranges.
+pub mod mls;
 /// SELinux paths.
 pub mod path;
 /// SELinux policies.
 pub mod policy;
+/// Spawning processes in SELinux security contexts.
+pub mod process;
+/// SELinux kernel status page.
+pub mod status;
 /// Utilities.
 pub mod utils;
 
@@ -78,6 +88,7 @@ use utils::*;
 
 /// Red, green and blue components of a color.
 #[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RGB {
     /// Red component.
     pub red: u8,
@@ -97,6 +108,7 @@ impl RGB {
 
 /// Background and foreground colors.
 #[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LayerColors {
     /// Background color.
     pub background: RGB,
@@ -117,6 +129,7 @@ impl LayerColors {
 
 /// Colors of a security context.
 #[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SecurityContextColors {
     /// Background and foreground colors of SELinux user.
     pub user: LayerColors,
@@ -319,6 +332,24 @@ impl<'context> SecurityContext<'context> {
         Self::from_result_with_name(proc_name, r, context, name, raw_format)
     }
 
+    /// Return an iterator over the names and contexts of all kernel initial
+    /// security identifiers.
+    ///
+    /// Names are listed from the `selinuxfs` mount point used by `libselinux`
+    /// to get the contexts, *i.e.*, [`path::selinux_fs_mount_point`].
+    ///
+    /// See: [`policy::initial_security_identifier_names`],
+    /// `security_get_initial_context()`.
+    #[doc(alias = "security_get_initial_context")]
+    pub fn initial_kernel_contexts(raw_format: bool) -> Result<InitialKernelContexts> {
+        let selinux_fs = path::selinux_fs_mount_point()?;
+        let names = policy::initial_security_identifier_names(Some(&selinux_fs))?;
+        Ok(InitialKernelContexts {
+            names: names.into_iter(),
+            raw_format,
+        })
+    }
+
     /// Get the default SELinux security context for the specified media type
     /// from the policy.
     ///
@@ -393,6 +424,47 @@ impl<'context> SecurityContext<'context> {
         Self::from_result("manual_user_enter_context()", r, context, raw_format)
     }
 
+    /// Build a context for the specified SELinux user from the answers
+    /// that `manual_user_enter_context()` would ask the user for, without
+    /// interacting with the terminal.
+    ///
+    /// If `the_type` is `None`, then the default type of `role` is used.
+    /// A `level` is required by policies supporting Multi Level Security.
+    /// The resulting context is validated by the security policy.
+    ///
+    /// See: `get_default_type()`, `security_check_context()`.
+    #[doc(alias = "manual_user_enter_context")]
+    pub fn of_se_user_with_answers(
+        se_user: &str,
+        role: &str,
+        the_type: Option<&str>,
+        level: Option<&str>,
+        raw_format: bool,
+    ) -> Result<Self> {
+        let the_type = match the_type {
+            Some(the_type) => the_type.into(),
+            None => {
+                let c_type = default_type_for_role(role)?;
+                str::from_utf8(c_type.as_c_str().to_bytes())?.into()
+            }
+        };
+
+        let range = level.map(ContextRange::from);
+        let parsed = ParsedContext::new(se_user.into(), role.into(), the_type, range);
+        let context = parsed.to_security_context(raw_format)?;
+
+        if context.check() == Some(true) {
+            Ok(context)
+        } else {
+            let err = io::ErrorKind::InvalidInput.into();
+            Err(Error::from_io_name(
+                "security_check_context()",
+                parsed.to_string(),
+                err,
+            ))
+        }
+    }
+
     /// Obtain a context, for the specified SELinux user identity, that is
     /// reachable from the specified `reachable_from_context`.
     ///
@@ -475,6 +547,100 @@ impl<'context> SecurityContext<'context> {
         Self::from_result(proc_name, r, context, raw_format)
     }
 
+    /// Obtain a session context, for the specified SELinux user identity,
+    /// with the requested `role` and, if specified, the requested `level`.
+    ///
+    /// The default contexts configuration is used if it provides a context for
+    /// `role`. Otherwise, the context is completed by the default type of
+    /// `role`, and it is returned only if the security policy allows `se_user`
+    /// to reach it from `reachable_from_context`, or from the context of the
+    /// current process if `reachable_from_context` is `None`.
+    /// If `level` is `None`, then the range of the default context of
+    /// `se_user` is used, if any.
+    ///
+    /// See: `get_ordered_context_list()`, `get_default_context_with_role()`,
+    /// `get_default_context_with_rolelevel()`, `get_default_type()`,
+    /// `security_compute_user()`.
+    #[doc(alias = "get_ordered_context_list")]
+    #[doc(alias = "get_default_context_with_role")]
+    #[doc(alias = "get_default_context_with_rolelevel")]
+    #[doc(alias = "get_default_type")]
+    pub fn session_for_se_user(
+        se_user: &str,
+        role: &str,
+        level: Option<&str>,
+        reachable_from_context: Option<&Self>,
+        raw_format: bool,
+    ) -> Result<Self> {
+        // Use the default contexts configuration, if it has a context for `role`.
+        let configured = SecurityContextList::of_se_user(se_user, level, reachable_from_context)?;
+        if configured
+            .selected_context(ContextSelection::Role(role))?
+            .is_some()
+        {
+            return Self::default_for_se_user(
+                se_user,
+                Some(role),
+                level,
+                reachable_from_context,
+                raw_format,
+            );
+        }
+
+        let level = match level {
+            Some(level) => Some(level.to_owned()),
+            None => {
+                let context = Self::default_for_se_user(
+                    se_user,
+                    None,
+                    None,
+                    reachable_from_context,
+                    raw_format,
+                )?;
+                let parsed = ParsedContext::try_from(&context)?;
+                parsed.range.map(|range| range.to_string())
+            }
+        };
+
+        let context =
+            Self::of_se_user_with_answers(se_user, role, None, level.as_deref(), raw_format)?;
+
+        // Contexts are compared in raw format.
+        let (current, converted_source, converted_context);
+        let source = match reachable_from_context {
+            Some(source) if source.is_raw_format() => source,
+
+            Some(source) => {
+                converted_source = source.to_raw_format()?;
+                &converted_source
+            }
+
+            None => {
+                current = Self::current(true)?;
+                &current
+            }
+        };
+
+        let raw_context = if raw_format {
+            &context
+        } else {
+            converted_context = context.to_raw_format()?;
+            &converted_context
+        };
+
+        let reachable = SecurityContextList::of_computed_user(se_user, source)?;
+        if reachable
+            .iter()
+            .any(|reachable| reachable.as_bytes() == raw_context.as_bytes())
+        {
+            Ok(context)
+        } else {
+            let err = io::ErrorKind::PermissionDenied.into();
+            let name = ParsedContext::try_from(&context)?.to_string();
+            Err(Error::from_io_name("security_compute_user()", name, err))
+        }
+    }
+
     /// Get the context used for executing a new process.
     ///
     /// See: `getexeccon()`.
@@ -777,7 +943,7 @@ impl<'context> SecurityContext<'context> {
         target_context: &Self,
         target_class: SecurityClass,
         requested_access: selinux_sys::access_vector_t,
-    ) -> Result<selinux_sys::av_decision> {
+    ) -> Result<AccessDecision> {
         if self.is_raw != target_context.is_raw {
             return Err(Error::SecurityContextFormatMismatch);
         }
@@ -804,7 +970,12 @@ impl<'context> SecurityContext<'context> {
         if r == -1_i32 {
             Err(Error::last_io_error(proc_name))
         } else {
-            Ok(unsafe { result.assume_init() })
+            let decision = unsafe { result.assume_init() };
+            Ok(AccessDecision::new(
+                target_class,
+                requested_access,
+                &decision,
+            ))
         }
     }
 
@@ -1153,6 +1324,14 @@ impl<'context> SecurityContext<'context> {
         }
     }
 
+    fn from_c_str_copy(c_context: &CStr, raw_format: bool) -> Result<Self> {
+        let context = unsafe { libc::strdup(c_context.as_ptr()) };
+        ptr::NonNull::new(context).map_or_else(
+            || Err(Error::last_io_error("strdup()")),
+            |context| Ok(Self::from_ptr(context, None, raw_format)),
+        )
+    }
+
     fn from_result(
         proc_name: &'static str,
         result: c_int,
@@ -1271,11 +1450,198 @@ impl<'context> Drop for SecurityContext<'context> {
     }
 }
 
+/// Owned SELinux security context.
+///
+/// Unlike [`SecurityContext`], this type owns a copy of the context in memory
+/// managed by Rust, so it can be cloned, compared, hashed, and sent to other
+/// threads.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct OwnedSecurityContext {
+    context: CString,
+    is_raw: bool,
+}
+
+impl OwnedSecurityContext {
+    /// Create a new instance.
+    #[must_use]
+    pub fn new(context: CString, raw_format: bool) -> Self {
+        Self {
+            context,
+            is_raw: raw_format,
+        }
+    }
+
+    /// Return `false` if security context translation must be performed.
+    #[must_use]
+    pub fn is_raw_format(&self) -> bool {
+        self.is_raw
+    }
+
+    /// Return the string value of this security context.
+    #[must_use]
+    pub fn as_c_str(&self) -> &CStr {
+        &self.context
+    }
+
+    /// Return the security context's byte slice.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.context.as_bytes()
+    }
+
+    /// Return the string value of this security context.
+    #[must_use]
+    pub fn into_c_string(self) -> CString {
+        self.context
+    }
+
+    /// Return a security context borrowing this context.
+    #[must_use]
+    pub fn as_security_context(&self) -> SecurityContext<'_> {
+        SecurityContext::from_c_str(&self.context, self.is_raw)
+    }
+
+    /// Perform context translation from the human-readable format (translated)
+    /// to the internal system format (raw).
+    ///
+    /// See: `selinux_trans_to_raw_context()`.
+    #[doc(alias = "selinux_trans_to_raw_context")]
+    pub fn to_raw_format(&self) -> Result<Self> {
+        Self::try_from(&self.as_security_context().to_raw_format()?)
+    }
+
+    /// Perform context translation from the internal system format (raw) to
+    /// the human-readable format (translated).
+    ///
+    /// See: `selinux_raw_to_trans_context()`.
+    #[doc(alias = "selinux_raw_to_trans_context")]
+    pub fn to_translated_format(&self) -> Result<Self> {
+        Self::try_from(&self.as_security_context().to_translated_format()?)
+    }
+}
+
+impl<'context> TryFrom<&SecurityContext<'context>> for OwnedSecurityContext {
+    type Error = Error;
+
+    /// Copy the security context.
+    ///
+    /// This fails if the context is empty.
+    fn try_from(context: &SecurityContext<'context>) -> Result<Self> {
+        match context.to_c_string()? {
+            Some(c_context) => Ok(Self::new(c_context.into_owned(), context.is_raw)),
+
+            None => {
+                let err = io::ErrorKind::InvalidData.into();
+                Err(Error::from_io("OwnedSecurityContext::try_from()", err))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'context> serde::Serialize for SecurityContext<'context> {
+    /// Serialize this security context as its string value, along with
+    /// its format.
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::{Error as _, SerializeStruct};
+
+        let context = match self.to_c_string().map_err(S::Error::custom)? {
+            Some(context) => context.to_str().map_err(S::Error::custom)?.to_owned(),
+            None => String::new(),
+        };
+
+        let mut state = serializer.serialize_struct("SecurityContext", 2)?;
+        state.serialize_field("context", &context)?;
+        state.serialize_field("raw", &self.is_raw)?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for OwnedSecurityContext {
+    /// Serialize this security context as its string value, along with
+    /// its format.
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.as_security_context().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for OwnedSecurityContext {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error as _;
+
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "SecurityContext", deny_unknown_fields)]
+        struct Fields {
+            context: String,
+            raw: bool,
+        }
+
+        let fields = Fields::deserialize(deserializer)?;
+        let context = CString::new(fields.context).map_err(D::Error::custom)?;
+        Ok(Self::new(context, fields.raw))
+    }
+}
+
+impl str::FromStr for OwnedSecurityContext {
+    type Err = Error;
+
+    /// Return a security context in the human-readable format (translated).
+    fn from_str(context: &str) -> Result<Self> {
+        if context.is_empty() {
+            let err = io::ErrorKind::InvalidInput.into();
+            return Err(Error::from_io("OwnedSecurityContext::from_str()", err));
+        }
+
+        str_to_c_string(context).map(|context| Self::new(context, false))
+    }
+}
+
+impl fmt::Display for OwnedSecurityContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.context.to_string_lossy())
+    }
+}
+
+/// Iterator over the names and contexts of kernel initial security identifiers.
+///
+/// See: [`SecurityContext::initial_kernel_contexts`].
+#[derive(Debug)]
+pub struct InitialKernelContexts {
+    names: std::vec::IntoIter<String>,
+    raw_format: bool,
+}
+
+impl Iterator for InitialKernelContexts {
+    type Item = Result<(String, SecurityContext<'static>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let name = self.names.next()?;
+        let context = SecurityContext::of_initial_kernel_context(&name, self.raw_format);
+        Some(context.map(|context| (name, context)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.names.size_hint()
+    }
+}
+
 /// List of security contexts.
 #[derive(Debug)]
 pub struct SecurityContextList {
     context_list: ptr::NonNull<*mut c_char>,
     count: usize,
+    is_raw: bool,
     _phantom_data: PhantomData<c_char>,
 }
 
@@ -1304,6 +1670,32 @@ impl SecurityContextList {
         self.count == 0
     }
 
+    /// Return `true` if the security contexts in this list are in raw format.
+    #[must_use]
+    pub fn is_raw_format(&self) -> bool {
+        self.is_raw
+    }
+
+    /// Return an iterator over the security contexts in this list.
+    ///
+    /// ⚠️ The returned contexts are **NOT** owned, see
+    /// [`SecurityContextList::get`].
+    #[must_use]
+    pub fn iter(&self) -> SecurityContextListIter<'_> {
+        SecurityContextListIter {
+            list: self,
+            front: 0,
+            back: self.count,
+        }
+    }
+
+    /// Copy all security contexts in this list.
+    pub fn to_vec(&self) -> Result<Vec<OwnedSecurityContext>> {
+        self.iter()
+            .map(|context| OwnedSecurityContext::try_from(&context))
+            .collect()
+    }
+
     /// Obtain a list of contexts, for the specified SELinux user identity,
     /// that are reachable from the specified `reachable_from_context`.
     ///
@@ -1356,6 +1748,64 @@ impl SecurityContextList {
                     Ok(Self {
                         context_list,
                         count: r as c_uint as usize,
+                        is_raw: false,
+                        _phantom_data: PhantomData,
+                    })
+                },
+            )
+        }
+    }
+
+    /// Obtain the list of contexts that the security policy allows the
+    /// specified SELinux user identity to reach from `source_context`.
+    ///
+    /// Unlike [`SecurityContextList::of_se_user`], this queries the kernel
+    /// security policy directly, ignoring the `default_contexts` configuration.
+    ///
+    /// See: `security_compute_user()`.
+    #[doc(alias = "security_compute_user")]
+    #[doc(alias = "security_compute_user_raw")]
+    pub fn of_computed_user(se_user: &str, source_context: &SecurityContext) -> Result<Self> {
+        let c_se_user = str_to_c_string(se_user)?;
+
+        let (proc, proc_name): (unsafe extern "C" fn(_, _, _) -> _, _) =
+            if source_context.is_raw_format() {
+                (
+                    selinux_sys::security_compute_user_raw,
+                    "security_compute_user_raw()",
+                )
+            } else {
+                (
+                    selinux_sys::security_compute_user,
+                    "security_compute_user()",
+                )
+            };
+
+        let mut context_list: *mut *mut c_char = ptr::null_mut();
+        let r = unsafe {
+            proc(
+                source_context.context.as_ptr(),
+                c_se_user.as_ptr(),
+                &mut context_list,
+            )
+        };
+
+        if r == -1_i32 {
+            Err(Error::last_io_error(proc_name))
+        } else {
+            ptr::NonNull::new(context_list).map_or_else(
+                || Err(Error::from_io(proc_name, io::ErrorKind::InvalidData.into())),
+                |context_list| {
+                    // The list is terminated by a null pointer.
+                    let mut count = 0_usize;
+                    while !unsafe { *context_list.as_ptr().wrapping_add(count) }.is_null() {
+                        count += 1;
+                    }
+
+                    Ok(Self {
+                        context_list,
+                        count,
+                        is_raw: source_context.is_raw_format(),
                         _phantom_data: PhantomData,
                     })
                 },
@@ -1386,6 +1836,45 @@ impl SecurityContextList {
         }
     }
 
+    /// Return the first security context of this list that matches `selection`,
+    /// without interacting with the user.
+    ///
+    /// ⚠️ The returned instance does **NOT** own the context, see
+    /// [`SecurityContextList::get`].
+    pub fn selected_context(
+        &self,
+        mut selection: ContextSelection<'_>,
+    ) -> Result<Option<SecurityContext<'_>>> {
+        for context in self {
+            let is_selected = match &mut selection {
+                ContextSelection::First => true,
+
+                ContextSelection::Role(role) => ParsedContext::try_from(&context)?.role == *role,
+
+                ContextSelection::Level(level) => {
+                    // Ranges are compared in raw format.
+                    let converted;
+                    let raw_context = if context.is_raw_format() {
+                        &context
+                    } else {
+                        converted = context.to_raw_format()?;
+                        &converted
+                    };
+
+                    let parsed = ParsedContext::try_from(raw_context)?;
+                    matches!(&parsed.range, Some(ContextRange::Mls(range)) if range.low() == *level)
+                }
+
+                ContextSelection::Custom(is_selected) => is_selected(&context),
+            };
+
+            if is_selected {
+                return Ok(Some(context));
+            }
+        }
+        Ok(None)
+    }
+
     /// Ask the user via `stdin`/`stdout` as to which context they want from
     /// this list of contexts, and return a new context as selected by the user.
     ///
@@ -1409,6 +1898,84 @@ impl Drop for SecurityContextList {
     }
 }
 
+impl<'list> IntoIterator for &'list SecurityContextList {
+    type Item = SecurityContext<'list>;
+    type IntoIter = SecurityContextListIter<'list>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Strategy selecting a security context from a [`SecurityContextList`].
+///
+/// See: [`SecurityContextList::selected_context`].
+pub enum ContextSelection<'selection> {
+    /// Select the first context.
+    First,
+    /// Select the first context having the given role.
+    Role(&'selection str),
+    /// Select the first context whose range starts at the given level.
+    Level(&'selection mls::MlsLevel),
+    /// Select the first context for which the closure returns `true`.
+    Custom(&'selection mut dyn FnMut(&SecurityContext) -> bool),
+}
+
+impl fmt::Debug for ContextSelection<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::First => f.write_str("First"),
+            Self::Role(role) => f.debug_tuple("Role").field(role).finish(),
+            Self::Level(level) => f.debug_tuple("Level").field(level).finish(),
+            Self::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+/// Iterator over the security contexts of a [`SecurityContextList`].
+#[derive(Debug, Clone)]
+pub struct SecurityContextListIter<'list> {
+    list: &'list SecurityContextList,
+    front: usize,
+    back: usize,
+}
+
+impl<'list> Iterator for SecurityContextListIter<'list> {
+    type Item = SecurityContext<'list>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.front < self.back {
+            let index = self.front;
+            self.front += 1;
+            if let Some(context) = self.list.get(index, self.list.is_raw) {
+                return Some(context);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<'list> DoubleEndedIterator for SecurityContextListIter<'list> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while self.front < self.back {
+            self.back -= 1;
+            if let Some(context) = self.list.get(self.back, self.list.is_raw) {
+                return Some(context);
+            }
+        }
+        None
+    }
+}
+
+impl<'list> ExactSizeIterator for SecurityContextListIter<'list> {}
+
+impl<'list> std::iter::FusedIterator for SecurityContextListIter<'list> {}
+
 /// File access mode.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct FileAccessMode(selinux_sys::mode_t);
@@ -1432,6 +1999,9 @@ impl FileAccessMode {
 }
 
 /// SELinux security class.
+///
+/// With the `serde` feature, a security class is serialized as its name,
+/// because its value depends on the loaded policy.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct SecurityClass(selinux_sys::security_class_t);
 
@@ -1541,6 +2111,59 @@ impl SecurityClass {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for SecurityClass {
+    /// Serialize this security class as its name.
+    ///
+    /// See: `security_class_to_string()`.
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::Error as _;
+
+        let name_ptr = unsafe { selinux_sys::security_class_to_string(self.0) };
+        if name_ptr.is_null() {
+            let err = io::ErrorKind::NotFound.into();
+            let err = Error::from_io("security_class_to_string()", err);
+            return Err(S::Error::custom(err));
+        }
+
+        let c_name = unsafe { CStr::from_ptr(name_ptr) };
+        serializer.serialize_str(c_name.to_str().map_err(S::Error::custom)?)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SecurityClass {
+    /// Deserialize a security class from its name.
+    ///
+    /// See: `string_to_security_class()`.
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error as _;
+
+        let name = String::deserialize(deserializer)?;
+        Self::from_name(&name).map_err(D::Error::custom)
+    }
+}
+
+impl TryFrom<selinux_sys::security_class_t> for SecurityClass {
+    type Error = Error;
+
+    fn try_from(class: selinux_sys::security_class_t) -> Result<Self> {
+        Self::new(class)
+    }
+}
+
+impl From<SecurityClass> for selinux_sys::security_class_t {
+    fn from(class: SecurityClass) -> Self {
+        class.0
+    }
+}
+
 impl TryFrom<FileAccessMode> for SecurityClass {
     type Error = Error;
 
@@ -1557,6 +2180,150 @@ impl TryFrom<FileAccessMode> for SecurityClass {
     }
 }
 
+/// Access decision computed by the security policy, for a set of requested
+/// permissions of a security class.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccessDecision {
+    class: SecurityClass,
+    requested: selinux_sys::access_vector_t,
+    allowed: selinux_sys::access_vector_t,
+    decided: selinux_sys::access_vector_t,
+    audit_allow: selinux_sys::access_vector_t,
+    audit_deny: selinux_sys::access_vector_t,
+    sequence_number: c_uint,
+    flags: u32,
+}
+
+impl AccessDecision {
+    pub(crate) fn new(
+        class: SecurityClass,
+        requested: selinux_sys::access_vector_t,
+        decision: &selinux_sys::av_decision,
+    ) -> Self {
+        Self {
+            class,
+            requested,
+            allowed: decision.allowed,
+            decided: decision.decided,
+            audit_allow: decision.auditallow,
+            audit_deny: decision.auditdeny,
+            sequence_number: decision.seqno,
+            flags: decision.flags,
+        }
+    }
+
+    /// Return the security class of the requested permissions.
+    #[must_use]
+    pub fn class(&self) -> SecurityClass {
+        self.class
+    }
+
+    /// Return the requested permissions.
+    #[must_use]
+    pub fn requested(&self) -> selinux_sys::access_vector_t {
+        self.requested
+    }
+
+    /// Return the permissions allowed by the security policy.
+    #[must_use]
+    pub fn allowed(&self) -> selinux_sys::access_vector_t {
+        self.allowed
+    }
+
+    /// Return the permissions decided by the security policy.
+    #[must_use]
+    pub fn decided(&self) -> selinux_sys::access_vector_t {
+        self.decided
+    }
+
+    /// Return `true` if all the given `permissions` are allowed.
+    #[must_use]
+    pub fn is_allowed(&self, permissions: selinux_sys::access_vector_t) -> bool {
+        self.allowed & permissions == permissions
+    }
+
+    /// Return the requested permissions that are not allowed.
+    #[must_use]
+    pub fn denied(&self) -> selinux_sys::access_vector_t {
+        self.requested & !self.allowed
+    }
+
+    /// Return the requested permissions that should be audited, depending on
+    /// whether access was `granted`.
+    #[must_use]
+    pub fn audited(&self, granted: bool) -> selinux_sys::access_vector_t {
+        if granted {
+            self.requested & self.audit_allow
+        } else {
+            self.denied() & self.audit_deny
+        }
+    }
+
+    /// Return `true` if the access should be audited, depending on whether
+    /// it was `granted`.
+    #[must_use]
+    pub fn should_audit(&self, granted: bool) -> bool {
+        self.audited(granted) != 0
+    }
+
+    /// Return `true` if the source domain is permissive, *i.e.*, denials are
+    /// audited but not enforced.
+    ///
+    /// See: `SELINUX_AVD_FLAGS_PERMISSIVE`.
+    #[must_use]
+    pub fn is_permissive_domain(&self) -> bool {
+        self.flags & selinux_sys::SELINUX_AVD_FLAGS_PERMISSIVE != 0
+    }
+
+    /// Return the sequence number of the security policy that computed
+    /// this decision.
+    #[must_use]
+    pub fn policy_seqno(&self) -> u32 {
+        self.sequence_number
+    }
+
+    /// Return the names of the requested permissions that are allowed.
+    ///
+    /// See: `security_av_perm_to_string()`.
+    #[doc(alias = "security_av_perm_to_string")]
+    pub fn allowed_permission_names(&self) -> Result<Vec<String>> {
+        self.permission_names(self.requested & self.allowed)
+    }
+
+    /// Return the names of the requested permissions that are not allowed.
+    ///
+    /// See: `security_av_perm_to_string()`.
+    #[doc(alias = "security_av_perm_to_string")]
+    pub fn denied_permission_names(&self) -> Result<Vec<String>> {
+        self.permission_names(self.denied())
+    }
+
+    fn permission_names(&self, permissions: selinux_sys::access_vector_t) -> Result<Vec<String>> {
+        (0..selinux_sys::access_vector_t::BITS)
+            .map(|bit| 1 << bit)
+            .filter(|&permission| permissions & permission != 0)
+            .map(|permission| {
+                let name = unsafe { self.class.access_vector_bit_name(permission) }?;
+                Ok(name.to_str()?.into())
+            })
+            .collect()
+    }
+}
+
+impl From<&AccessDecision> for selinux_sys::av_decision {
+    fn from(decision: &AccessDecision) -> Self {
+        Self {
+            allowed: decision.allowed,
+            decided: decision.decided,
+            auditallow: decision.audit_allow,
+            auditdeny: decision.audit_deny,
+            seqno: decision.sequence_number,
+            flags: decision.flags,
+        }
+    }
+}
+
 /// Opaque security context.
 #[derive(Debug)]
 pub struct OpaqueSecurityContext {
@@ -1676,6 +2443,22 @@ impl OpaqueSecurityContext {
         self.set(selinux_sys::context_range_set, proc_name, new_value)
     }
 
+    /// Return this security context's range, parsed as an MLS range.
+    ///
+    /// See: `context_range_get()`.
+    #[doc(alias = "context_range_get")]
+    pub fn mls_range(&self) -> Result<mls::MlsRange> {
+        self.range()?.to_str()?.parse()
+    }
+
+    /// Set the range of this security context to an MLS range.
+    ///
+    /// See: `context_range_set()`.
+    #[doc(alias = "context_range_set")]
+    pub fn set_mls_range(&self, new_value: &mls::MlsRange) -> Result<()> {
+        self.set_range_str(&new_value.to_string())
+    }
+
     /// Return the string value of this security context's role.
     ///
     /// See: `context_role_get()`.
@@ -1787,9 +2570,139 @@ impl fmt::Display for OpaqueSecurityContext {
     }
 }
 
+/// Range of a security context.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ContextRange {
+    /// MLS range, *e.g.*, `s0-s15:c0.c1023`.
+    Mls(mls::MlsRange),
+    /// Range that does not follow the MLS syntax, *e.g.*, a translated range,
+    /// kept as written.
+    Other(String),
+}
+
+impl From<&str> for ContextRange {
+    /// Parse `range` as an MLS range if it follows the MLS syntax,
+    /// and keep it as written otherwise.
+    fn from(range: &str) -> Self {
+        match range.parse() {
+            Ok(range) => Self::Mls(range),
+            Err(_) => Self::Other(range.into()),
+        }
+    }
+}
+
+impl fmt::Display for ContextRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Mls(range) => write!(f, "{range}"),
+            Self::Other(range) => write!(f, "{range}"),
+        }
+    }
+}
+
+/// Security context parsed into its components.
+///
+/// Parsing and formatting are performed without calling `libselinux`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParsedContext {
+    /// SELinux user.
+    pub user: String,
+    /// SELinux role.
+    pub role: String,
+    /// SELinux type.
+    pub the_type: String,
+    /// Range, if any.
+    pub range: Option<ContextRange>,
+}
+
+impl ParsedContext {
+    /// Create a new instance.
+    #[must_use]
+    pub fn new(user: String, role: String, the_type: String, range: Option<ContextRange>) -> Self {
+        Self {
+            user,
+            role,
+            the_type,
+            range,
+        }
+    }
+
+    /// Return a new security context holding this context.
+    pub fn to_security_context(&self, raw_format: bool) -> Result<SecurityContext<'static>> {
+        let c_context = str_to_c_string(&self.to_string())?;
+        SecurityContext::from_c_str_copy(&c_context, raw_format)
+    }
+
+    /// Return a new opaque security context holding this context.
+    ///
+    /// See: `context_new()`.
+    #[doc(alias = "context_new")]
+    pub fn to_opaque_security_context(&self) -> Result<OpaqueSecurityContext> {
+        OpaqueSecurityContext::new(&self.to_string())
+    }
+}
+
+impl str::FromStr for ParsedContext {
+    type Err = Error;
+
+    /// Parse a security context of the form `user:role:type[:range]`.
+    ///
+    /// The range might contain colons.
+    fn from_str(context: &str) -> Result<Self> {
+        let is_valid = |component: &&str| !component.is_empty() && !component.contains('\0');
+        let components: Vec<&str> = context.splitn(4, ':').collect();
+
+        if components.len() >= 3 && components.iter().all(is_valid) {
+            let (user, role, the_type) = (components[0], components[1], components[2]);
+            let range = components.get(3).map(|range| ContextRange::from(*range));
+            Ok(Self::new(user.into(), role.into(), the_type.into(), range))
+        } else {
+            let err = io::ErrorKind::InvalidInput.into();
+            Err(Error::from_io_name(
+                "ParsedContext::from_str()",
+                context,
+                err,
+            ))
+        }
+    }
+}
+
+impl fmt::Display for ParsedContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.user, self.role, self.the_type)?;
+        match &self.range {
+            Some(range) => write!(f, ":{range}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'context> TryFrom<&SecurityContext<'context>> for ParsedContext {
+    type Error = Error;
+
+    fn try_from(context: &SecurityContext<'context>) -> Result<Self> {
+        str::from_utf8(context.as_bytes())?
+            .trim_end_matches('\0')
+            .parse()
+    }
+}
+
+impl TryFrom<&OpaqueSecurityContext> for ParsedContext {
+    type Error = Error;
+
+    /// See: `context_str()`.
+    #[doc(alias = "context_str")]
+    fn try_from(context: &OpaqueSecurityContext) -> Result<Self> {
+        context.to_c_string()?.to_str()?.parse()
+    }
+}
+
 /// Support of SELinux in the running kernel.
 #[non_exhaustive]
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum KernelSupport {
     /// SELinux is unsupported.
     Unsupported,
@@ -1802,6 +2715,7 @@ pub enum KernelSupport {
 /// SELinux enforcing mode.
 #[non_exhaustive]
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SELinuxMode {
     /// SELinux is not enforcing.
     NotRunning,
@@ -1814,6 +2728,7 @@ pub enum SELinuxMode {
 /// SELinux handling of undefined object classes and permissions.
 #[non_exhaustive]
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum UndefinedHandling {
     /// Undefined object classes and permissions are allowed.
     Allowed,
@@ -1826,6 +2741,7 @@ pub enum UndefinedHandling {
 /// Protection checked by SELinux on `mmap()` and `mprotect()` calls.
 #[non_exhaustive]
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ProtectionCheckingMode {
     /// Actual protection that will be applied by the kernel
     /// (including the effects of `READ_IMPLIES_EXEC`).
@@ -2023,6 +2939,145 @@ where
     ret_val_to_result("selinux_set_mapping()", r)
 }
 
+/// Define a security class and its permissions, as a [`bitflags`] type.
+///
+/// Each permission is given a bit in the order of declaration, which matches
+/// the dynamic mapping established by [`set_dynamic_mapping`] from the
+/// generated `MAPPING` entry. The generated type provides:
+/// - `CLASS_NAME`: the name of the security class.
+/// - `PERMISSION_NAMES`: the names of the permissions, in bit order.
+/// - `MAPPING`: an entry suitable for [`set_dynamic_mapping`].
+/// - `set_dynamic_mapping()`: establish a dynamic mapping of this class alone.
+/// - `security_class()`: return the [`SecurityClass`] of this class.
+///
+/// Up to 32 permissions can be defined, and every permission name must be
+/// unique, otherwise compilation fails.
+///
+/// ⚠️ Each call to [`set_dynamic_mapping`] replaces the previous mapping, so
+/// multiple classes must be mapped together, *e.g.*,
+/// `set_dynamic_mapping(&[File::MAPPING, Process::MAPPING])`.
+///
+/// # Example
+///
+/// ```
+/// selinux::security_class! {
+///     /// Permissions of the `file` security class.
+///     pub struct File: "file" {
+///         /// Read the file.
+///         const READ = "read";
+///         /// Write the file.
+///         const WRITE = "write";
+///     }
+/// }
+///
+/// assert_eq!(File::READ.bits(), 1);
+/// assert_eq!(File::WRITE.bits(), 2);
+/// assert_eq!(File::MAPPING, ("file", &["read", "write"] as &[&str]));
+/// ```
+#[macro_export]
+macro_rules! security_class {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident: $class:literal {
+            $(
+                $(#[$permission_attr:meta])*
+                const $permission:ident = $permission_name:literal;
+            )*
+        }
+    ) => {
+        $crate::__private::bitflags::bitflags! {
+            $(#[$attr])*
+            #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+            $vis struct $name: $crate::__private::access_vector_t {
+                $(
+                    $(#[$permission_attr])*
+                    const $permission = 1 << $crate::__private::permission_index(
+                        $name::PERMISSION_NAMES,
+                        $permission_name,
+                    );
+                )*
+            }
+        }
+
+        #[allow(dead_code)]
+        impl $name {
+            /// Name of the security class.
+            pub const CLASS_NAME: &'static str = $class;
+
+            /// Names of the permissions, in bit order.
+            pub const PERMISSION_NAMES: &'static [&'static str] = &[$($permission_name),*];
+
+            /// Dynamic mapping entry of this security class.
+            pub const MAPPING: (&'static str, &'static [&'static str]) =
+                (Self::CLASS_NAME, Self::PERMISSION_NAMES);
+
+            /// Establish a dynamic mapping of this security class alone.
+            ///
+            /// See: `selinux_set_mapping()`.
+            pub fn set_dynamic_mapping() -> $crate::errors::Result<()> {
+                $crate::set_dynamic_mapping(&[Self::MAPPING])
+            }
+
+            /// Return the security class.
+            ///
+            /// See: `string_to_security_class()`.
+            pub fn security_class() -> $crate::errors::Result<$crate::SecurityClass> {
+                $crate::SecurityClass::from_name(Self::CLASS_NAME)
+            }
+        }
+
+        impl ::core::convert::From<$name> for $crate::__private::access_vector_t {
+            fn from(permissions: $name) -> Self {
+                permissions.bits()
+            }
+        }
+
+        // Evaluate every permission bit, so that invalid definitions fail to compile.
+        const _: $name = $name::all();
+    };
+}
+
+#[doc(hidden)]
+pub mod __private {
+    pub use bitflags;
+    pub use selinux_sys::access_vector_t;
+
+    /// Return the index of `name` in `names`, which must appear exactly once.
+    #[must_use]
+    pub const fn permission_index(names: &[&str], name: &str) -> u32 {
+        let mut result = None;
+        let mut index = 0;
+        while index < names.len() {
+            if str_eq(names[index], name) {
+                assert!(result.is_none(), "duplicate permission name");
+                result = Some(index as u32);
+            }
+            index += 1;
+        }
+
+        match result {
+            Some(result) => result,
+            None => panic!("unknown permission name"),
+        }
+    }
+
+    const fn str_eq(left: &str, right: &str) -> bool {
+        let (left, right) = (left.as_bytes(), right.as_bytes());
+        if left.len() != right.len() {
+            return false;
+        }
+
+        let mut index = 0;
+        while index < left.len() {
+            if left[index] != right[index] {
+                return false;
+            }
+            index += 1;
+        }
+        true
+    }
+}
+
 /// Flush the SELinux class cache, *e.g.*, upon a policy reload.
 ///
 /// This function requires `libselinux` version `3.1` or later.
@@ -0,0 +1,490 @@
+#[cfg(test)]
+mod tests;
+
+use std::{cmp, fmt, io, str};
+
+use crate::errors::{Error, Result};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// MLS sensitivity, *e.g.*, `s0`.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Sensitivity(u16);
+
+impl Sensitivity {
+    /// Create a new instance.
+    #[must_use]
+    pub fn new(value: u16) -> Self {
+        Self(value)
+    }
+
+    /// Return the numeric value of this sensitivity.
+    #[must_use]
+    pub fn value(&self) -> u16 {
+        self.0
+    }
+}
+
+impl str::FromStr for Sensitivity {
+    type Err = Error;
+
+    /// Parse a sensitivity of the form `s<number>`.
+    fn from_str(s: &str) -> Result<Self> {
+        parse_number(s, 's', "Sensitivity::from_str()").map(Self)
+    }
+}
+
+impl fmt::Display for Sensitivity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "s{}", self.0)
+    }
+}
+
+/// Set of MLS categories, *e.g.*, `c0.c5,c9`.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
+pub struct CategorySet {
+    // Bit `i % 64` of word `i / 64` is set if category `i` is in the set.
+    // Trailing zero words are never stored, so that equal sets compare equal.
+    words: Vec<u64>,
+}
+
+impl CategorySet {
+    /// Create a new empty set.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return `true` if this set contains no categories.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Return the number of categories in this set.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Return `true` if `category` is in this set.
+    #[must_use]
+    pub fn contains(&self, category: u16) -> bool {
+        let (index, mask) = Self::position(category);
+        matches!(self.words.get(index), Some(word) if word & mask != 0)
+    }
+
+    /// Add `category` to this set.
+    ///
+    /// Return `true` if `category` was not already in this set.
+    pub fn insert(&mut self, category: u16) -> bool {
+        let (index, mask) = Self::position(category);
+        if self.words.len() <= index {
+            self.words.resize(index + 1, 0);
+        }
+
+        let was_absent = self.words[index] & mask == 0;
+        self.words[index] |= mask;
+        was_absent
+    }
+
+    /// Remove `category` from this set.
+    ///
+    /// Return `true` if `category` was in this set.
+    pub fn remove(&mut self, category: u16) -> bool {
+        let (index, mask) = Self::position(category);
+        let was_present = match self.words.get_mut(index) {
+            Some(word) if *word & mask != 0 => {
+                *word &= !mask;
+                true
+            }
+
+            _ => false,
+        };
+
+        self.normalize();
+        was_present
+    }
+
+    /// Iterate over the categories in this set, in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = u16> + '_ {
+        self.words.iter().enumerate().flat_map(|(index, &word)| {
+            (0..WORD_BITS)
+                .filter(move |bit| word & (1_u64 << bit) != 0)
+                .map(move |bit| (index * WORD_BITS + bit) as u16)
+        })
+    }
+
+    /// Return `true` if every category in this set is also in `other`.
+    #[must_use]
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.words.len() <= other.words.len()
+            && self
+                .words
+                .iter()
+                .zip(&other.words)
+                .all(|(&word, &other_word)| word & !other_word == 0)
+    }
+
+    /// Return `true` if every category in `other` is also in this set.
+    #[must_use]
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Return the set of categories that are in this set, in `other`, or in both.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let (longer, shorter) = if self.words.len() >= other.words.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+
+        let mut words = longer.words.clone();
+        for (word, &other_word) in words.iter_mut().zip(&shorter.words) {
+            *word |= other_word;
+        }
+        Self { words }
+    }
+
+    /// Return the set of categories that are both in this set and in `other`.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let words = self
+            .words
+            .iter()
+            .zip(&other.words)
+            .map(|(&word, &other_word)| word & other_word)
+            .collect();
+
+        let mut result = Self { words };
+        result.normalize();
+        result
+    }
+
+    fn position(category: u16) -> (usize, u64) {
+        let category = usize::from(category);
+        (category / WORD_BITS, 1_u64 << (category % WORD_BITS))
+    }
+
+    fn normalize(&mut self) {
+        while self.words.last() == Some(&0) {
+            self.words.pop();
+        }
+    }
+}
+
+impl FromIterator<u16> for CategorySet {
+    fn from_iter<I: IntoIterator<Item = u16>>(iter: I) -> Self {
+        let mut result = Self::new();
+        for category in iter {
+            result.insert(category);
+        }
+        result
+    }
+}
+
+impl str::FromStr for CategorySet {
+    type Err = Error;
+
+    /// Parse a comma-separated list of categories, or ranges of categories,
+    /// *e.g.*, `c0.c5,c9`.
+    fn from_str(s: &str) -> Result<Self> {
+        let proc_name = "CategorySet::from_str()";
+        let mut result = Self::new();
+
+        for item in s.split(',') {
+            let (first, last) = match item.split_once('.') {
+                Some((first, last)) => (first, last),
+                None => (item, item),
+            };
+
+            let first = parse_number(first, 'c', proc_name)?;
+            let last = parse_number(last, 'c', proc_name)?;
+            if first > last {
+                let err = io::ErrorKind::InvalidInput.into();
+                return Err(Error::from_io_name(proc_name, s, err));
+            }
+
+            for category in first..=last {
+                result.insert(category);
+            }
+        }
+        Ok(result)
+    }
+}
+
+impl fmt::Display for CategorySet {
+    /// Format this set the way the kernel does: runs of two or more
+    /// categories are written as ranges, *e.g.*, `c0.c2,c4.c5,c7`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut categories = self.iter().peekable();
+        let mut separator = "";
+
+        while let Some(first) = categories.next() {
+            let mut last = first;
+            while let Some(&next) = categories.peek() {
+                if Some(next) != last.checked_add(1) {
+                    break;
+                }
+                last = next;
+                categories.next();
+            }
+
+            write!(f, "{separator}c{first}")?;
+            if last != first {
+                write!(f, ".c{last}")?;
+            }
+            separator = ",";
+        }
+        Ok(())
+    }
+}
+
+/// MLS level, made of a sensitivity and a set of categories, *e.g.*, `s0:c0.c5`.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
+pub struct MlsLevel {
+    /// Sensitivity.
+    pub sensitivity: Sensitivity,
+    /// Categories.
+    pub categories: CategorySet,
+}
+
+impl MlsLevel {
+    /// Create a new instance.
+    #[must_use]
+    pub fn new(sensitivity: Sensitivity, categories: CategorySet) -> Self {
+        Self {
+            sensitivity,
+            categories,
+        }
+    }
+
+    /// Return `true` if this level dominates `other`, *i.e.*, its sensitivity
+    /// is greater than or equal to that of `other`, and its categories are
+    /// a superset of those of `other`.
+    #[must_use]
+    pub fn dominates(&self, other: &Self) -> bool {
+        self.sensitivity >= other.sensitivity && self.categories.is_superset(&other.categories)
+    }
+
+    /// Return `true` if this level and `other` dominate each other.
+    #[must_use]
+    pub fn equals(&self, other: &Self) -> bool {
+        self == other
+    }
+
+    /// Return `true` if neither this level nor `other` dominates the other.
+    #[must_use]
+    pub fn incomparable(&self, other: &Self) -> bool {
+        !self.dominates(other) && !other.dominates(self)
+    }
+
+    /// Return the lowest level that dominates both this level and `other`.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            sensitivity: cmp::max(self.sensitivity, other.sensitivity),
+            categories: self.categories.union(&other.categories),
+        }
+    }
+
+    /// Return the highest level that both this level and `other` dominate.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self {
+            sensitivity: cmp::min(self.sensitivity, other.sensitivity),
+            categories: self.categories.intersection(&other.categories),
+        }
+    }
+}
+
+impl PartialOrd for MlsLevel {
+    /// Levels are partially ordered by dominance.
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        match (self.dominates(other), other.dominates(self)) {
+            (true, true) => Some(cmp::Ordering::Equal),
+            (true, false) => Some(cmp::Ordering::Greater),
+            (false, true) => Some(cmp::Ordering::Less),
+            (false, false) => None,
+        }
+    }
+}
+
+impl str::FromStr for MlsLevel {
+    type Err = Error;
+
+    /// Parse a level of the form `s<number>[:<categories>]`.
+    fn from_str(s: &str) -> Result<Self> {
+        let (sensitivity, categories) = match s.split_once(':') {
+            Some((sensitivity, categories)) => (sensitivity, categories.parse()?),
+            None => (s, CategorySet::new()),
+        };
+
+        Ok(Self::new(sensitivity.parse()?, categories))
+    }
+}
+
+impl fmt::Display for MlsLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.sensitivity)?;
+        if !self.categories.is_empty() {
+            write!(f, ":{}", self.categories)?;
+        }
+        Ok(())
+    }
+}
+
+/// MLS range, made of a low and a high level, *e.g.*, `s0-s15:c0.c1023`.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
+pub struct MlsRange {
+    low: MlsLevel,
+    high: MlsLevel,
+}
+
+impl MlsRange {
+    /// Create a new instance.
+    ///
+    /// `high` must dominate `low`.
+    pub fn new(low: MlsLevel, high: MlsLevel) -> Result<Self> {
+        if high.dominates(&low) {
+            Ok(Self { low, high })
+        } else {
+            let err = io::ErrorKind::InvalidInput.into();
+            Err(Error::from_io_name(
+                "MlsRange::new()",
+                format!("{low}-{high}"),
+                err,
+            ))
+        }
+    }
+
+    /// Return the low level of this range.
+    #[must_use]
+    pub fn low(&self) -> &MlsLevel {
+        &self.low
+    }
+
+    /// Return the high level of this range.
+    #[must_use]
+    pub fn high(&self) -> &MlsLevel {
+        &self.high
+    }
+
+    /// Return `true` if this range contains `level`.
+    #[must_use]
+    pub fn contains_level(&self, level: &MlsLevel) -> bool {
+        level.dominates(&self.low) && self.high.dominates(level)
+    }
+
+    /// Return `true` if this range contains every level of `other`.
+    #[must_use]
+    pub fn contains(&self, other: &Self) -> bool {
+        self.contains_level(&other.low) && self.contains_level(&other.high)
+    }
+
+    /// Return `true` if this range dominates `other`, *i.e.*, each level of
+    /// this range dominates the corresponding level of `other`.
+    #[must_use]
+    pub fn dominates(&self, other: &Self) -> bool {
+        self.low.dominates(&other.low) && self.high.dominates(&other.high)
+    }
+
+    /// Return `true` if this range and `other` dominate each other.
+    #[must_use]
+    pub fn equals(&self, other: &Self) -> bool {
+        self == other
+    }
+
+    /// Return `true` if neither this range nor `other` dominates the other.
+    #[must_use]
+    pub fn incomparable(&self, other: &Self) -> bool {
+        !self.dominates(other) && !other.dominates(self)
+    }
+
+    /// Return the smallest range containing both this range and `other`.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            low: self.low.intersection(&other.low),
+            high: self.high.union(&other.high),
+        }
+    }
+
+    /// Return the largest range contained in both this range and `other`,
+    /// if any.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let low = self.low.union(&other.low);
+        let high = self.high.intersection(&other.high);
+        Self::new(low, high).ok()
+    }
+}
+
+impl From<MlsLevel> for MlsRange {
+    /// Return a range made of a single level.
+    fn from(level: MlsLevel) -> Self {
+        Self {
+            low: level.clone(),
+            high: level,
+        }
+    }
+}
+
+impl str::FromStr for MlsRange {
+    type Err = Error;
+
+    /// Parse a range of the form `<low level>[-<high level>]`.
+    fn from_str(s: &str) -> Result<Self> {
+        match s.split_once('-') {
+            Some((low, high)) => Self::new(low.parse()?, high.parse()?),
+            None => s.parse::<MlsLevel>().map(Self::from),
+        }
+    }
+}
+
+impl fmt::Display for MlsRange {
+    /// Format this range, omitting the high level if it equals the low level.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.low)?;
+        if self.high != self.low {
+            write!(f, "-{}", self.high)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for MlsRange {
+    /// Serialize this range as its string value.
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for MlsRange {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error as _;
+
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(D::Error::custom)
+    }
+}
+
+fn parse_number(s: &str, prefix: char, proc_name: &'static str) -> Result<u16> {
+    s.strip_prefix(prefix)
+        .filter(|digits| !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()))
+        .and_then(|digits| digits.parse().ok())
+        .ok_or_else(|| Error::from_io_name(proc_name, s, io::ErrorKind::InvalidInput.into()))
+}
@@ -0,0 +1,140 @@
+#![cfg(all(test, target_os = "linux", not(target_env = "kernel")))]
+
+use assert_matches::assert_matches;
+
+#[test]
+fn sensitivity() {
+    let sensitivity: super::Sensitivity = "s15".parse().unwrap();
+    assert_eq!(sensitivity, super::Sensitivity::new(15));
+    assert_eq!(sensitivity.value(), 15);
+    assert_eq!(sensitivity.to_string(), "s15");
+    assert!(super::Sensitivity::new(0) < sensitivity);
+
+    for invalid in ["", "s", "15", "c15", "s-1", "s+1", "s1a", "s65536"] {
+        let err = invalid.parse::<super::Sensitivity>().unwrap_err();
+        assert_matches!(err, crate::errors::Error::IO1Name { .. });
+    }
+}
+
+#[test]
+fn category_set() {
+    let set: super::CategorySet = "c0.c5,c9".parse().unwrap();
+    assert_eq!(set.len(), 7);
+    assert!(set.contains(0) && set.contains(5) && set.contains(9));
+    assert!(!set.contains(6) && !set.contains(1000));
+    assert_eq!(set.iter().collect::<Vec<_>>(), [0, 1, 2, 3, 4, 5, 9]);
+    assert_eq!(set.to_string(), "c0.c5,c9");
+
+    let set: super::CategorySet = "c3,c1,c2,c7,c8,c1023".parse().unwrap();
+    assert_eq!(set.to_string(), "c1.c3,c7.c8,c1023");
+
+    let set: super::CategorySet = [65535_u16, 65534].into_iter().collect();
+    assert_eq!(set.to_string(), "c65534.c65535");
+
+    let mut set = super::CategorySet::new();
+    assert!(set.is_empty());
+    assert_eq!(set.to_string(), "");
+    assert!(set.insert(200));
+    assert!(!set.insert(200));
+    assert!(set.remove(200));
+    assert!(!set.remove(200));
+    assert_eq!(set, super::CategorySet::new());
+
+    for invalid in ["", "c", "c1,", "c5.c1", "c1.c2.c3", "s1", "c1;c2"] {
+        let err = invalid.parse::<super::CategorySet>().unwrap_err();
+        assert_matches!(err, crate::errors::Error::IO1Name { .. });
+    }
+}
+
+#[test]
+fn category_set_algebra() {
+    let a: super::CategorySet = "c0.c5".parse().unwrap();
+    let b: super::CategorySet = "c4.c9,c200".parse().unwrap();
+
+    assert_eq!(a.union(&b).to_string(), "c0.c9,c200");
+    assert_eq!(a.intersection(&b).to_string(), "c4.c5");
+    assert!(a.intersection(&"c100".parse().unwrap()).is_empty());
+
+    assert!(a.intersection(&b).is_subset(&a));
+    assert!(a.union(&b).is_superset(&b));
+    assert!(!a.is_subset(&b));
+    assert!(super::CategorySet::new().is_subset(&a));
+}
+
+#[test]
+fn mls_level() {
+    let level: super::MlsLevel = "s1:c0.c5".parse().unwrap();
+    assert_eq!(level.sensitivity, super::Sensitivity::new(1));
+    assert_eq!(level.categories.len(), 6);
+    assert_eq!(level.to_string(), "s1:c0.c5");
+
+    let low: super::MlsLevel = "s0".parse().unwrap();
+    assert!(low.categories.is_empty());
+    assert_eq!(low.to_string(), "s0");
+
+    assert!(level.dominates(&low));
+    assert!(!low.dominates(&level));
+    assert!(level > low);
+    assert!(level.equals(&level.clone()));
+
+    let other: super::MlsLevel = "s2:c9".parse().unwrap();
+    assert!(level.incomparable(&other));
+    assert_eq!(level.partial_cmp(&other), None);
+    assert_eq!(level.union(&other).to_string(), "s2:c0.c5,c9");
+    assert_eq!(level.intersection(&other).to_string(), "s1");
+
+    for invalid in ["", "s0:", "c0", "s0:c0:c1", "s0-s1"] {
+        invalid.parse::<super::MlsLevel>().unwrap_err();
+    }
+}
+
+#[test]
+fn mls_range() {
+    let range: super::MlsRange = "s0-s15:c0.c1023".parse().unwrap();
+    assert_eq!(range.low().to_string(), "s0");
+    assert_eq!(range.high().to_string(), "s15:c0.c1023");
+    assert_eq!(range.to_string(), "s0-s15:c0.c1023");
+
+    let single: super::MlsRange = "s0:c1,c2".parse().unwrap();
+    assert_eq!(single.low(), single.high());
+    assert_eq!(single.to_string(), "s0:c1.c2");
+    assert_eq!(single, super::MlsRange::from(single.low().clone()));
+
+    assert!(range.contains(&single));
+    assert!(!single.contains(&range));
+    assert!(range.contains_level(&"s3:c7".parse().unwrap()));
+    assert!(range.dominates(&"s0".parse().unwrap()));
+    assert!(!range.dominates(&single));
+    assert!(range.equals(&range.clone()));
+
+    let a: super::MlsRange = "s0:c1-s1:c1".parse().unwrap();
+    let b: super::MlsRange = "s0:c2-s1:c2".parse().unwrap();
+    assert!(a.incomparable(&b));
+    assert_eq!(a.union(&b).to_string(), "s0-s1:c1.c2");
+    assert_eq!(a.intersection(&b), None);
+
+    let c: super::MlsRange = "s1-s3".parse().unwrap();
+    let d: super::MlsRange = "s0-s2".parse().unwrap();
+    assert_eq!(c.intersection(&d).unwrap().to_string(), "s1-s2");
+
+    let err = "s1-s0".parse::<super::MlsRange>().unwrap_err();
+    assert_matches!(err, crate::errors::Error::IO1Name { .. });
+    "s0:c1-s1".parse::<super::MlsRange>().unwrap_err();
+    "SystemLow-SystemHigh"
+        .parse::<super::MlsRange>()
+        .unwrap_err();
+}
+
+#[test]
+fn opaque_security_context_mls_range() {
+    let context = crate::OpaqueSecurityContext::new("user_u:user_r:user_t:s0").unwrap();
+    assert_eq!(context.mls_range().unwrap().to_string(), "s0");
+
+    let range: super::MlsRange = "s0-s0:c1.c3".parse().unwrap();
+    context.set_mls_range(&range).unwrap();
+    assert_eq!(context.range().unwrap().to_str().unwrap(), "s0-s0:c1.c3");
+    assert_eq!(context.mls_range().unwrap(), range);
+
+    let parsed = crate::ParsedContext::try_from(&context).unwrap();
+    assert_eq!(parsed.range, Some(crate::ContextRange::Mls(range)));
+}
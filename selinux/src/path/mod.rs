@@ -1,10 +1,13 @@
 #[cfg(test)]
 mod tests;
 
-use std::path::Path;
+use std::ffi::{CStr, OsStr};
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
 
-use crate::errors::Result;
-use crate::utils::get_static_path;
+use crate::errors::{Error, Result};
+use crate::utils::{get_static_path, os_str_to_c_string};
 
 /// Return the top-level SELinux configuration directory.
 ///
@@ -245,3 +248,35 @@ pub fn netfilter_context() -> Result<&'static Path> {
     let proc_name = "selinux_netfilter_context_path()";
     get_static_path(selinux_sys::selinux_netfilter_context_path, proc_name)
 }
+
+/// Return the mount point of the SELinux file system (`selinuxfs`), as found
+/// by `libselinux`, or as set by [`set_selinux_fs_mount_point`].
+///
+/// This fails if `selinuxfs` is not mounted.
+///
+/// See: `selinux_mnt`.
+#[doc(alias = "selinux_mnt")]
+pub fn selinux_fs_mount_point() -> Result<PathBuf> {
+    let mount_point = unsafe { selinux_sys::selinux_mnt };
+    if mount_point.is_null() {
+        let err = io::ErrorKind::NotFound.into();
+        Err(Error::from_io("selinux_mnt", err))
+    } else {
+        let mount_point = unsafe { CStr::from_ptr(mount_point) };
+        Ok(PathBuf::from(OsStr::from_bytes(mount_point.to_bytes())))
+    }
+}
+
+/// Set the mount point of the SELinux file system (`selinuxfs`), overriding
+/// the one found by `libselinux`, *e.g.*, before `selinuxfs` is mounted.
+///
+/// This should be called before other threads use `libselinux`, because
+/// the previous mount point is freed.
+///
+/// See: `set_selinuxmnt()`.
+#[doc(alias = "set_selinuxmnt")]
+pub fn set_selinux_fs_mount_point(path: impl AsRef<Path>) -> Result<()> {
+    let c_path = os_str_to_c_string(path.as_ref().as_os_str())?;
+    unsafe { selinux_sys::set_selinuxmnt(c_path.as_ptr()) };
+    Ok(())
+}
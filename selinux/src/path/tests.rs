@@ -209,3 +209,15 @@ fn netfilter_context() {
     assert!(!path.as_os_str().is_empty());
     //assert!(path.exists() || crate::current_mode() == crate::SELinuxMode::NotRunning);
 }
+
+#[test]
+fn selinux_fs_mount_point() {
+    match super::selinux_fs_mount_point() {
+        Ok(path) => {
+            assert!(path.is_absolute());
+            assert!(path.exists());
+        }
+
+        Err(_err) => assert_eq!(crate::current_mode(), crate::SELinuxMode::NotRunning),
+    }
+}
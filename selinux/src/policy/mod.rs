@@ -2,8 +2,8 @@
 mod tests;
 
 use std::os::raw::{c_char, c_int, c_uint, c_void};
-use std::path::Path;
-use std::{io, ptr};
+use std::path::{Path, PathBuf};
+use std::{fs, io, ptr};
 
 use crate::errors::{Error, Result};
 use crate::utils::*;
@@ -107,3 +107,153 @@ pub fn binary_policy_path() -> Result<&'static Path> {
     let proc_name = "selinux_binary_policy_path()";
     get_static_path(selinux_sys::selinux_binary_policy_path, proc_name)
 }
+
+/// Security class defined by the loaded policy.
+///
+/// Class values and permission bits are those of the kernel, and are not
+/// affected by [`crate::set_dynamic_mapping`].
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct ClassDefinition {
+    value: selinux_sys::security_class_t,
+    name: String,
+    permissions: Vec<PermissionDefinition>,
+}
+
+impl ClassDefinition {
+    /// Return the kernel value of this security class.
+    #[must_use]
+    pub fn value(&self) -> selinux_sys::security_class_t {
+        self.value
+    }
+
+    /// Return the name of this security class.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Return the permissions of this security class, sorted by access vector bit.
+    #[must_use]
+    pub fn permissions(&self) -> &[PermissionDefinition] {
+        &self.permissions
+    }
+}
+
+/// Permission of a security class defined by the loaded policy.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct PermissionDefinition {
+    access_vector_bit: selinux_sys::access_vector_t,
+    name: String,
+}
+
+impl PermissionDefinition {
+    /// Return the kernel access vector bit of this permission.
+    #[must_use]
+    pub fn access_vector_bit(&self) -> selinux_sys::access_vector_t {
+        self.access_vector_bit
+    }
+
+    /// Return the name of this permission.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Return all security classes defined by the loaded policy, along with
+/// their permissions, sorted by class value.
+///
+/// Definitions are read from `<selinux_fs>/class/`. If `selinux_fs` is `None`,
+/// then the mount point returned by [`crate::path::selinux_fs_mount_point`]
+/// is used.
+pub fn security_classes(selinux_fs: Option<&Path>) -> Result<Vec<ClassDefinition>> {
+    let selinux_fs = match selinux_fs {
+        Some(selinux_fs) => selinux_fs.to_path_buf(),
+        None => crate::path::selinux_fs_mount_point()?,
+    };
+
+    let mut classes = read_dir_names(&selinux_fs.join("class"))?
+        .into_iter()
+        .map(|(name, path)| {
+            let value = read_number(&path.join("index"))?;
+
+            let mut permissions = read_dir_names(&path.join("perms"))?
+                .into_iter()
+                .map(|(name, path)| {
+                    let bit_index = read_number::<u32>(&path)?.wrapping_sub(1);
+                    let access_vector_bit = 1_u32.checked_shl(bit_index).ok_or_else(|| {
+                        Error::from_io_path("str::parse()", &path, invalid_data())
+                    })?;
+
+                    Ok(PermissionDefinition {
+                        access_vector_bit,
+                        name,
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            permissions.sort_unstable();
+
+            Ok(ClassDefinition {
+                value,
+                name,
+                permissions,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    classes.sort_unstable();
+    Ok(classes)
+}
+
+/// Return the names of all kernel initial security identifiers, sorted.
+///
+/// Names are read from `<selinux_fs>/initial_contexts/`. If `selinux_fs` is
+/// `None`, then the mount point returned by
+/// [`crate::path::selinux_fs_mount_point`] is used, which is also where
+/// `security_get_initial_context()` reads contexts from.
+pub fn initial_security_identifier_names(selinux_fs: Option<&Path>) -> Result<Vec<String>> {
+    let selinux_fs = match selinux_fs {
+        Some(selinux_fs) => selinux_fs.to_path_buf(),
+        None => crate::path::selinux_fs_mount_point()?,
+    };
+
+    let mut names: Vec<String> = read_dir_names(&selinux_fs.join("initial_contexts"))?
+        .into_iter()
+        .map(|(name, _path)| name)
+        .collect();
+    names.sort_unstable();
+    Ok(names)
+}
+
+fn read_dir_names(path: &Path) -> Result<Vec<(String, PathBuf)>> {
+    let entries =
+        fs::read_dir(path).map_err(|err| Error::from_io_path("std::fs::read_dir()", path, err))?;
+
+    entries
+        .map(|entry| {
+            let entry =
+                entry.map_err(|err| Error::from_io_path("std::fs::read_dir()", path, err))?;
+
+            match entry.file_name().into_string() {
+                Ok(name) => Ok((name, entry.path())),
+                Err(_name) => Err(Error::from_io_path(
+                    "OsString::into_string()",
+                    entry.path(),
+                    invalid_data(),
+                )),
+            }
+        })
+        .collect()
+}
+
+fn read_number<T: std::str::FromStr>(path: &Path) -> Result<T> {
+    let text = fs::read_to_string(path)
+        .map_err(|err| Error::from_io_path("std::fs::read_to_string()", path, err))?;
+
+    text.trim()
+        .parse()
+        .map_err(|_err| Error::from_io_path("str::parse()", path, invalid_data()))
+}
+
+fn invalid_data() -> io::Error {
+    io::ErrorKind::InvalidData.into()
+}
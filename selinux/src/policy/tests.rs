@@ -63,3 +63,68 @@ fn set_root_path() {
     let path = super::current_policy_path().unwrap();
     super::set_root_path(path).unwrap();
 }
+
+#[test]
+fn security_classes() {
+    match super::security_classes(None) {
+        Ok(classes) => {
+            let file = classes.iter().find(|class| class.name() == "file").unwrap();
+            assert_ne!(file.value(), 0);
+
+            let read = file.permissions().iter().find(|p| p.name() == "read");
+            assert_eq!(read.unwrap().access_vector_bit().count_ones(), 1);
+        }
+
+        Err(_err) => assert_eq!(crate::current_mode(), crate::SELinuxMode::NotRunning),
+    }
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let write = |path: &str, contents: &str| {
+        let path = dir.path().join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    };
+
+    write("class/process/index", "2\n");
+    write("class/process/perms/fork", "1");
+    write("class/process/perms/signal", "3");
+    write("class/file/index", "1\n");
+    write("class/file/perms/write", "2");
+    write("class/file/perms/read", "1");
+
+    let classes = super::security_classes(Some(dir.path())).unwrap();
+    assert_eq!(classes.len(), 2);
+    assert_eq!((classes[0].name(), classes[0].value()), ("file", 1));
+    assert_eq!((classes[1].name(), classes[1].value()), ("process", 2));
+
+    let permissions: Vec<_> = classes[1]
+        .permissions()
+        .iter()
+        .map(|p| (p.name(), p.access_vector_bit()))
+        .collect();
+    assert_eq!(permissions, [("fork", 1), ("signal", 4)]);
+
+    write("class/file/perms/bad", "0");
+    super::security_classes(Some(dir.path())).unwrap_err();
+
+    let dir = tempfile::TempDir::new().unwrap();
+    super::security_classes(Some(dir.path())).unwrap_err();
+}
+
+#[test]
+fn initial_security_identifier_names() {
+    match super::initial_security_identifier_names(None) {
+        Ok(names) => assert!(names.iter().any(|name| name == "kernel")),
+        Err(_err) => assert_eq!(crate::current_mode(), crate::SELinuxMode::NotRunning),
+    }
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let initial_contexts = dir.path().join("initial_contexts");
+    std::fs::create_dir(&initial_contexts).unwrap();
+    for name in ["unlabeled", "kernel", "file"] {
+        std::fs::write(initial_contexts.join(name), "").unwrap();
+    }
+
+    let names = super::initial_security_identifier_names(Some(dir.path())).unwrap();
+    assert_eq!(names, ["file", "kernel", "unlabeled"]);
+}
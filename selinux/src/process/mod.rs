@@ -0,0 +1,121 @@
+#[cfg(test)]
+mod tests;
+
+use std::ffi::CStr;
+use std::io;
+use std::os::unix::process::CommandExt as _;
+use std::process::Command;
+
+use crate::errors::{Error, Result};
+use crate::{SELinuxMode, SecurityClass, SecurityContext};
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for std::process::Command {}
+}
+
+/// SELinux extensions to [`std::process::Command`].
+///
+/// This trait is sealed, and cannot be implemented outside of this crate.
+pub trait CommandExt: private::Sealed {
+    /// Execute the child process in the given security context.
+    ///
+    /// The context used for the next `execve()` call is set in the child
+    /// process only, after it is forked, so the calling thread is not
+    /// affected.
+    ///
+    /// The security policy is queried before returning, and this fails if
+    /// the current process is not allowed to transition to `context`, unless
+    /// the current domain or the system is permissive.
+    /// If setting the context fails in the child process, then spawning the
+    /// child process fails.
+    ///
+    /// See: `setexeccon()`, `security_compute_av_flags()`.
+    #[doc(alias = "setexeccon")]
+    fn selinux_exec_context(&mut self, context: &SecurityContext) -> Result<&mut Self>;
+}
+
+impl CommandExt for Command {
+    fn selinux_exec_context(&mut self, context: &SecurityContext) -> Result<&mut Self> {
+        // The kernel only accepts raw contexts.
+        let raw_context;
+        let context = if context.is_raw_format() {
+            context
+        } else {
+            raw_context = context.to_raw_format()?;
+            &raw_context
+        };
+
+        let c_context = match context.to_c_string()? {
+            Some(c_context) => c_context.into_owned(),
+
+            None => {
+                let err = io::ErrorKind::InvalidInput.into();
+                return Err(Error::from_io("setexeccon()", err));
+            }
+        };
+
+        check_transition(context, &c_context)?;
+
+        // Only async-signal-safe functions can be called after forking.
+        let set_exec_context = move || write_exec_context(&c_context);
+        unsafe { self.pre_exec(set_exec_context) };
+        Ok(self)
+    }
+}
+
+fn check_transition(context: &SecurityContext, c_context: &CStr) -> Result<()> {
+    // Executing in the current context requires no transition.
+    let current = SecurityContext::current(true)?;
+    if matches!(current.to_c_string()?, Some(current) if current.as_ref() == c_context) {
+        return Ok(());
+    }
+
+    let class = SecurityClass::from_name("process")?;
+    let transition = class.access_vector_bit("transition")?;
+    let decision = current.query_access_decision(context, class, transition)?;
+
+    if decision.is_allowed(transition)
+        || decision.is_permissive_domain()
+        || crate::current_mode() == SELinuxMode::Permissive
+    {
+        Ok(())
+    } else {
+        let name = c_context.to_string_lossy();
+        let err = io::ErrorKind::PermissionDenied.into();
+        Err(Error::from_io_name(
+            "security_compute_av_flags()",
+            name,
+            err,
+        ))
+    }
+}
+
+/// Write `context` to the exec attribute of the current process, which must
+/// be single-threaded.
+fn write_exec_context(context: &CStr) -> io::Result<()> {
+    let path = b"/proc/self/attr/exec\0";
+    let fd = unsafe { libc::open(path.as_ptr().cast(), libc::O_WRONLY | libc::O_CLOEXEC) };
+    if fd == -1_i32 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let bytes = context.to_bytes_with_nul();
+    let result = loop {
+        let r = unsafe { libc::write(fd, bytes.as_ptr().cast(), bytes.len()) };
+        if r == -1 {
+            let err = io::Error::last_os_error();
+            if err.kind() != io::ErrorKind::Interrupted {
+                break Err(err);
+            }
+        } else if r as usize == bytes.len() {
+            break Ok(());
+        } else {
+            break Err(io::ErrorKind::WriteZero.into());
+        }
+    };
+
+    unsafe { libc::close(fd) };
+    result
+}
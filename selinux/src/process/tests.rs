@@ -0,0 +1,78 @@
+#![cfg(all(test, target_os = "linux", not(target_env = "kernel")))]
+
+use std::process::Command;
+
+use assert_matches::assert_matches;
+
+use super::CommandExt;
+
+#[test]
+fn command_selinux_exec_context() {
+    for raw_format in [false, true] {
+        let context = crate::SecurityContext::current(raw_format).unwrap();
+
+        let mut command = Command::new("id");
+        command.arg("-Z");
+        command.selinux_exec_context(&context).unwrap();
+
+        let output = command.output().unwrap();
+        assert!(output.status.success());
+
+        // `id -Z` prints the translated context.
+        let translated = crate::SecurityContext::current(false).unwrap();
+        let expected = translated.to_c_string().unwrap().unwrap();
+        let actual = String::from_utf8(output.stdout).unwrap();
+        assert_eq!(actual.trim_end(), expected.to_str().unwrap());
+    }
+
+    // The exec context of the calling thread is unchanged.
+    assert!(crate::SecurityContext::of_next_exec(true)
+        .unwrap()
+        .is_none());
+
+    let context = crate::SecurityContext::from_c_str(
+        std::ffi::CStr::from_bytes_with_nul(b"invalid_u:invalid_r:invalid_t:s0\0").unwrap(),
+        true,
+    );
+    Command::new("true")
+        .selinux_exec_context(&context)
+        .unwrap_err();
+}
+
+#[test]
+fn command_selinux_exec_context_transition_denied() {
+    // The context of a file is valid, but is not a domain that processes
+    // can transition to.
+    let context = crate::SecurityContext::of_path("/", true, true)
+        .unwrap()
+        .unwrap();
+
+    let current = crate::SecurityContext::current(true).unwrap();
+    let class = crate::SecurityClass::from_name("process").unwrap();
+    let transition = class.access_vector_bit("transition").unwrap();
+    let decision = current
+        .query_access_decision(&context, class, transition)
+        .unwrap();
+    assert!(!decision.is_allowed(transition));
+
+    let mut command = Command::new("true");
+    let result = command.selinux_exec_context(&context).map(|_| ());
+
+    if decision.is_permissive_domain() || crate::current_mode() == crate::SELinuxMode::Permissive {
+        result.unwrap();
+    } else {
+        // The error is reported before the child process is spawned.
+        let err = result.unwrap_err();
+        assert_matches!(
+            err,
+            crate::errors::Error::IO1Name {
+                operation: "security_compute_av_flags()",
+                ..
+            }
+        );
+        assert_eq!(
+            err.io_source().unwrap().kind(),
+            std::io::ErrorKind::PermissionDenied
+        );
+    }
+}
@@ -0,0 +1,487 @@
+#[cfg(test)]
+mod tests;
+
+use std::collections::VecDeque;
+use std::mem::MaybeUninit;
+use std::os::raw::c_int;
+use std::sync::{Mutex, MutexGuard, Once, PoisonError};
+use std::{io, mem, ptr};
+
+use reference_counted_singleton::{RCSRef, RefCountedSingleton};
+
+use crate::avc::lock_libselinux;
+use crate::errors::{Error, Result};
+use crate::SELinuxMode;
+
+/// SELinux kernel status page, mapped into the memory of the current process.
+///
+/// Querying the status page does not require a system call, unless the kernel
+/// does not support it, and a netlink socket is used as a fallback.
+///
+/// The status page mapping is shared with the
+/// [`AccessVectorCache`](crate::avc::AccessVectorCache), and it remains mapped
+/// as long as either of them uses it. Queries are serialized with the
+/// operations on the access vector cache, because `libselinux` updates it
+/// when the status changes.
+#[derive(Debug, PartialEq, Eq)]
+pub struct StatusPage {
+    uses_fallback: bool,
+}
+
+static STATUS_PAGE_INIT: Once = Once::new();
+static mut STATUS_PAGE: MaybeUninit<RefCountedSingleton<StatusPage>> = MaybeUninit::uninit();
+
+fn get_or_init_status_page() -> &'static RefCountedSingleton<StatusPage> {
+    STATUS_PAGE_INIT.call_once(|| unsafe {
+        STATUS_PAGE = MaybeUninit::new(RefCountedSingleton::default());
+    });
+
+    unsafe {
+        STATUS_PAGE
+            .as_ptr()
+            .as_ref()
+            .expect("Static must have a valid address")
+    }
+}
+
+impl StatusPage {
+    /// Map the SELinux kernel status page into the memory of the current
+    /// process.
+    ///
+    /// If the kernel does not support the status page, and `fallback` is `true`,
+    /// then status changes are detected through a netlink socket instead.
+    ///
+    /// Opening the status page while it is still opened succeeds, and returns
+    /// the already opened status page.
+    ///
+    /// See: `selinux_status_open()`.
+    #[doc(alias = "selinux_status_open")]
+    pub fn open(fallback: bool) -> Result<RCSRef<'static, Self>> {
+        let status_page = get_or_init_status_page();
+
+        let result = status_page.get_or_init(|| {
+            let mut users = lock_status_page_users();
+            let fallback = c_int::from(fallback);
+
+            let _guard = lock_libselinux();
+            match unsafe { selinux_sys::selinux_status_open(fallback) } {
+                -1_i32 => Err(Error::last_io_error("selinux_status_open()")),
+
+                r => {
+                    users.status_page = Some(fallback);
+                    Ok(Self {
+                        uses_fallback: r != 0_i32,
+                    })
+                }
+            }
+        });
+
+        match result {
+            Ok(value) => Ok(value),
+
+            Err(None) => Err(Error::LockPoisoned {
+                operation: "RefCountedSingleton::get_or_init()",
+            }),
+
+            Err(Some(err)) => Err(err),
+        }
+    }
+
+    /// Return `true` if status changes are detected through a netlink socket,
+    /// instead of the kernel status page.
+    #[must_use]
+    pub fn uses_fallback(&self) -> bool {
+        self.uses_fallback
+    }
+
+    /// Return `true` if the status changed since the last call to this
+    /// function.
+    ///
+    /// If the status changed, then the call backs
+    /// [`EnforcingChange`](crate::call_back::EnforcingChange) and
+    /// [`SecurityPolicyReload`](crate::call_back::SecurityPolicyReload) are
+    /// invoked, if they are set.
+    ///
+    /// The last observed status is shared by the whole process, and
+    /// [`Events`] calls this function too.
+    ///
+    /// ⚠️ Call backs are invoked while holding the lock serializing
+    /// the operations on the [`AccessVectorCache`](crate::avc::AccessVectorCache).
+    /// They must not use the access vector cache, nor this status page,
+    /// otherwise a dead lock occurs.
+    ///
+    /// See: `selinux_status_updated()`.
+    #[doc(alias = "selinux_status_updated")]
+    pub fn updated(&self) -> Result<bool> {
+        let _guard = lock_libselinux();
+        match unsafe { selinux_sys::selinux_status_updated() } {
+            -1_i32 => Err(Error::last_io_error("selinux_status_updated()")),
+            0_i32 => Ok(false),
+            _ => Ok(true),
+        }
+    }
+
+    /// Return the current SELinux enforcing mode.
+    ///
+    /// See: `selinux_status_getenforce()`.
+    #[doc(alias = "selinux_status_getenforce")]
+    pub fn current_mode(&self) -> Result<SELinuxMode> {
+        let _guard = lock_libselinux();
+        match unsafe { selinux_sys::selinux_status_getenforce() } {
+            -1_i32 => Err(Error::last_io_error("selinux_status_getenforce()")),
+            0_i32 => Ok(SELinuxMode::Permissive),
+            _ => Ok(SELinuxMode::Enforcing),
+        }
+    }
+
+    /// Return the sequence number of policy loads, which is incremented each
+    /// time a security policy is loaded.
+    ///
+    /// See: `selinux_status_policyload()`.
+    #[doc(alias = "selinux_status_policyload")]
+    pub fn policy_load_sequence_number(&self) -> Result<u32> {
+        let _guard = lock_libselinux();
+        let r = unsafe { selinux_sys::selinux_status_policyload() };
+        if r == -1_i32 {
+            Err(Error::last_io_error("selinux_status_policyload()"))
+        } else {
+            Ok(r as u32)
+        }
+    }
+
+    /// Return `true` if undefined object classes and permissions are denied.
+    ///
+    /// See: `selinux_status_deny_unknown()`.
+    #[doc(alias = "selinux_status_deny_unknown")]
+    pub fn denies_unknown(&self) -> Result<bool> {
+        let _guard = lock_libselinux();
+        match unsafe { selinux_sys::selinux_status_deny_unknown() } {
+            -1_i32 => Err(Error::last_io_error("selinux_status_deny_unknown()")),
+            0_i32 => Ok(false),
+            _ => Ok(true),
+        }
+    }
+
+    /// Return an iterator over SELinux events occurring after this call.
+    ///
+    /// Events are received from the kernel through a dedicated netlink
+    /// socket, so every enforcing mode change and policy load is reported,
+    /// even if several occur between two calls to the iterator.
+    /// If the kernel drops notifications because they were not received in
+    /// time, then only the latest enforcing mode and policy load, as read from
+    /// the status page, are reported.
+    ///
+    /// The returned iterator blocks until an event occurs.
+    /// Use [`Events::try_next`] to check for events without blocking.
+    pub fn events(&self) -> Result<Events<'_>> {
+        // Open the socket before reading the status, so that no event is missed.
+        let socket = NetlinkSocket::open()?;
+        self.updated()?;
+
+        Ok(Events {
+            status_page: self,
+            socket,
+            mode: self.current_mode()?,
+            policy_load_sequence_number: self.policy_load_sequence_number()?,
+            pending: VecDeque::with_capacity(2),
+        })
+    }
+}
+
+impl Drop for StatusPage {
+    /// See: `selinux_status_close()`.
+    #[doc(alias = "selinux_status_close")]
+    fn drop(&mut self) {
+        let mut users = lock_status_page_users();
+        users.status_page = None;
+
+        // Otherwise, the status page is unmapped when the access vector cache
+        // is destroyed.
+        if !users.access_vector_cache {
+            let _guard = lock_libselinux();
+            unsafe { selinux_sys::selinux_status_close() };
+        }
+    }
+}
+
+/// Users of the status page mapping.
+///
+/// `avc_open()` maps the status page, and `avc_destroy()` unmaps it, even if
+/// a [`StatusPage`] still uses it.
+#[derive(Debug)]
+struct StatusPageUsers {
+    /// `fallback` argument of `selinux_status_open()`, if a [`StatusPage`]
+    /// is opened.
+    status_page: Option<c_int>,
+    access_vector_cache: bool,
+}
+
+static STATUS_PAGE_USERS: Mutex<StatusPageUsers> = Mutex::new(StatusPageUsers {
+    status_page: None,
+    access_vector_cache: false,
+});
+
+fn lock_status_page_users() -> MutexGuard<'static, StatusPageUsers> {
+    STATUS_PAGE_USERS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Initialize the access vector cache by calling `open`, which might map the
+/// status page.
+pub(crate) fn open_access_vector_cache(open: impl FnOnce() -> Result<()>) -> Result<()> {
+    let mut users = lock_status_page_users();
+    open()?;
+    users.access_vector_cache = true;
+    Ok(())
+}
+
+/// Destroy the access vector cache by calling `destroy`, which unmaps the
+/// status page, then map it again if a [`StatusPage`] still uses it.
+///
+/// See: `selinux_status_open()`.
+pub(crate) fn destroy_access_vector_cache(destroy: impl FnOnce()) {
+    let mut users = lock_status_page_users();
+    destroy();
+    users.access_vector_cache = false;
+
+    if let Some(fallback) = users.status_page {
+        // On failure, queries of the status page report errors.
+        let _guard = lock_libselinux();
+        unsafe { selinux_sys::selinux_status_open(fallback) };
+    }
+}
+
+/// SELinux event.
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum SELinuxEvent {
+    /// SELinux enforcing mode changed to the given mode.
+    EnforcingChanged(SELinuxMode),
+    /// A security policy was loaded, with the given sequence number.
+    PolicyLoaded(u32),
+}
+
+/// Iterator over SELinux events, returned by [`StatusPage::events`].
+#[derive(Debug)]
+pub struct Events<'status> {
+    status_page: &'status StatusPage,
+    socket: NetlinkSocket,
+    mode: SELinuxMode,
+    policy_load_sequence_number: u32,
+    pending: VecDeque<SELinuxEvent>,
+}
+
+impl<'status> Events<'status> {
+    /// Return the next event if one occurred, without blocking.
+    pub fn try_next(&mut self) -> Result<Option<SELinuxEvent>> {
+        if self.pending.is_empty() {
+            self.poll()?;
+        }
+        Ok(self.pending.pop_front())
+    }
+
+    fn poll(&mut self) -> Result<()> {
+        // Process status changes first, so that the status page is up to date,
+        // even when it is emulated through a netlink socket.
+        self.status_page.updated()?;
+
+        let mut buffer = [0_u8; 1024];
+        let mut is_synchronized = true;
+        loop {
+            match self.socket.receive(&mut buffer) {
+                Ok(None) => break,
+
+                Ok(Some(len)) => {
+                    let first = self.pending.len();
+                    push_netlink_events(&buffer[..len], &mut self.pending);
+                    for event in self.pending.range(first..) {
+                        match *event {
+                            SELinuxEvent::EnforcingChanged(mode) => self.mode = mode,
+                            SELinuxEvent::PolicyLoaded(number) => {
+                                self.policy_load_sequence_number = number;
+                            }
+                        }
+                    }
+                }
+
+                // The kernel dropped some notifications.
+                Err(err) if err.raw_os_error() == Some(libc::ENOBUFS) => is_synchronized = false,
+
+                Err(err) => return Err(Error::from_io("recv()", err)),
+            }
+        }
+
+        if is_synchronized {
+            Ok(())
+        } else {
+            self.synchronize()
+        }
+    }
+
+    /// Report the differences between the status page and the last reported
+    /// events.
+    fn synchronize(&mut self) -> Result<()> {
+        let policy_load_sequence_number = self.status_page.policy_load_sequence_number()?;
+        if policy_load_sequence_number != self.policy_load_sequence_number {
+            self.policy_load_sequence_number = policy_load_sequence_number;
+            let event = SELinuxEvent::PolicyLoaded(policy_load_sequence_number);
+            self.pending.push_back(event);
+        }
+
+        let mode = self.status_page.current_mode()?;
+        if mode != self.mode {
+            self.mode = mode;
+            self.pending.push_back(SELinuxEvent::EnforcingChanged(mode));
+        }
+        Ok(())
+    }
+}
+
+impl<'status> Iterator for Events<'status> {
+    type Item = Result<SELinuxEvent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.try_next() {
+                Ok(Some(event)) => break Some(Ok(event)),
+
+                Ok(None) => {
+                    if let Err(err) = self.socket.wait() {
+                        break Some(Err(err));
+                    }
+                }
+
+                Err(err) => break Some(Err(err)),
+            }
+        }
+    }
+}
+
+// Kernel SELinux netlink multicast group and messages.
+// See: `linux/selinux_netlink.h`.
+const SELNLGRP_AVC: u32 = 1;
+const SELNL_MSG_SETENFORCE: u16 = 0x10;
+const SELNL_MSG_POLICYLOAD: u16 = 0x11;
+
+/// Netlink socket receiving SELinux notifications from the kernel.
+#[derive(Debug)]
+struct NetlinkSocket(c_int);
+
+impl NetlinkSocket {
+    fn open() -> Result<Self> {
+        let fd = unsafe {
+            libc::socket(
+                libc::AF_NETLINK,
+                libc::SOCK_RAW | libc::SOCK_CLOEXEC,
+                libc::NETLINK_SELINUX,
+            )
+        };
+        if fd == -1_i32 {
+            return Err(Error::last_io_error("socket()"));
+        }
+        let socket = Self(fd);
+
+        let mut address: libc::sockaddr_nl = unsafe { mem::zeroed() };
+        address.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+        address.nl_groups = SELNLGRP_AVC;
+        let address_len = mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t;
+
+        let r = unsafe { libc::bind(fd, ptr::addr_of!(address).cast(), address_len) };
+        if r == -1_i32 {
+            Err(Error::last_io_error("bind()"))
+        } else {
+            Ok(socket)
+        }
+    }
+
+    /// Receive one datagram, without blocking.
+    fn receive(&self, buffer: &mut [u8]) -> io::Result<Option<usize>> {
+        loop {
+            let r = unsafe {
+                libc::recv(
+                    self.0,
+                    buffer.as_mut_ptr().cast(),
+                    buffer.len(),
+                    libc::MSG_DONTWAIT,
+                )
+            };
+
+            if let Ok(len) = usize::try_from(r) {
+                break Ok(Some(len));
+            }
+
+            let err = io::Error::last_os_error();
+            match err.kind() {
+                io::ErrorKind::Interrupted => {}
+                io::ErrorKind::WouldBlock => break Ok(None),
+                _ => break Err(err),
+            }
+        }
+    }
+
+    /// Block until a datagram can be received.
+    fn wait(&self) -> Result<()> {
+        let mut poll_fd = libc::pollfd {
+            fd: self.0,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+
+        loop {
+            if unsafe { libc::poll(&mut poll_fd, 1, -1) } != -1_i32 {
+                break Ok(());
+            }
+
+            let err = io::Error::last_os_error();
+            if err.kind() != io::ErrorKind::Interrupted {
+                break Err(Error::from_io("poll()", err));
+            }
+        }
+    }
+}
+
+impl Drop for NetlinkSocket {
+    fn drop(&mut self) {
+        unsafe { libc::close(self.0) };
+    }
+}
+
+/// Append the events carried by the netlink `messages` to `events`.
+fn push_netlink_events(mut messages: &[u8], events: &mut VecDeque<SELinuxEvent>) {
+    let header_len = mem::size_of::<libc::nlmsghdr>();
+
+    while messages.len() >= header_len {
+        let header = unsafe { ptr::read_unaligned(messages.as_ptr().cast::<libc::nlmsghdr>()) };
+        let message_len = header.nlmsg_len as usize;
+        if message_len < header_len || message_len > messages.len() {
+            break;
+        }
+
+        let value = messages[header_len..message_len]
+            .get(..4)
+            .and_then(|bytes| <[u8; 4]>::try_from(bytes).ok());
+
+        match (header.nlmsg_type, value) {
+            (SELNL_MSG_SETENFORCE, Some(value)) => {
+                let mode = if i32::from_ne_bytes(value) == 0_i32 {
+                    SELinuxMode::Permissive
+                } else {
+                    SELinuxMode::Enforcing
+                };
+                events.push_back(SELinuxEvent::EnforcingChanged(mode));
+            }
+
+            (SELNL_MSG_POLICYLOAD, Some(value)) => {
+                let number = u32::from_ne_bytes(value);
+                events.push_back(SELinuxEvent::PolicyLoaded(number));
+            }
+
+            _ => {}
+        }
+
+        // Messages are aligned to 4 bytes.
+        let aligned_len = (message_len + 3) & !3;
+        messages = messages.get(aligned_len..).unwrap_or_default();
+    }
+}
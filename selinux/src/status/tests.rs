@@ -0,0 +1,123 @@
+#![cfg(all(test, target_os = "linux", not(target_env = "kernel")))]
+
+use std::collections::VecDeque;
+
+use serial_test::serial;
+
+#[serial]
+#[test]
+fn status_page_open() {
+    match super::StatusPage::open(true) {
+        Ok(status_page0) => {
+            let _ignored = format!("{status_page0:?}");
+
+            let status_page1 = super::StatusPage::open(true).unwrap();
+            assert_eq!(status_page0, status_page1);
+        }
+
+        Err(_err) => assert_eq!(crate::current_mode(), crate::SELinuxMode::NotRunning),
+    }
+}
+
+#[serial]
+#[test]
+fn status_page_values() {
+    if let Ok(status_page) = super::StatusPage::open(true) {
+        let _updated = status_page.updated().unwrap();
+        let _uses_fallback = status_page.uses_fallback();
+
+        assert_eq!(status_page.current_mode().unwrap(), crate::current_mode());
+
+        let _seqno = status_page.policy_load_sequence_number().unwrap();
+        let _denies_unknown = status_page.denies_unknown().unwrap();
+    }
+}
+
+#[serial]
+#[test]
+fn status_page_events() {
+    if let Ok(status_page) = super::StatusPage::open(true) {
+        let mut events = status_page.events().unwrap();
+        let _ignored = format!("{events:?}");
+        let _event = events.try_next().unwrap();
+    }
+
+    let event = super::SELinuxEvent::PolicyLoaded(1);
+    let _ignored = format!("{event:?}");
+    assert_ne!(
+        event,
+        super::SELinuxEvent::EnforcingChanged(crate::SELinuxMode::Enforcing)
+    );
+}
+
+#[test]
+fn netlink_events() {
+    fn message(message_type: u16, value: [u8; 4]) -> Vec<u8> {
+        let header = libc::nlmsghdr {
+            nlmsg_len: 20,
+            nlmsg_type: message_type,
+            nlmsg_flags: 0,
+            nlmsg_seq: 0,
+            nlmsg_pid: 0,
+        };
+
+        let mut message = Vec::with_capacity(20);
+        message.extend_from_slice(&header.nlmsg_len.to_ne_bytes());
+        message.extend_from_slice(&header.nlmsg_type.to_ne_bytes());
+        message.extend_from_slice(&header.nlmsg_flags.to_ne_bytes());
+        message.extend_from_slice(&header.nlmsg_seq.to_ne_bytes());
+        message.extend_from_slice(&header.nlmsg_pid.to_ne_bytes());
+        message.extend_from_slice(&value);
+        message
+    }
+
+    let mut messages = message(super::SELNL_MSG_SETENFORCE, 0_i32.to_ne_bytes());
+    messages.extend(message(super::SELNL_MSG_POLICYLOAD, 7_u32.to_ne_bytes()));
+    messages.extend(message(0x30, 0_u32.to_ne_bytes()));
+    messages.extend(message(super::SELNL_MSG_SETENFORCE, 1_i32.to_ne_bytes()));
+    messages.extend([1_u8, 2, 3]);
+
+    let mut events = VecDeque::new();
+    super::push_netlink_events(&messages, &mut events);
+    assert_eq!(
+        events,
+        [
+            super::SELinuxEvent::EnforcingChanged(crate::SELinuxMode::Permissive),
+            super::SELinuxEvent::PolicyLoaded(7),
+            super::SELinuxEvent::EnforcingChanged(crate::SELinuxMode::Enforcing),
+        ]
+    );
+}
+
+#[serial]
+#[test]
+fn status_page_outlives_access_vector_cache() {
+    if let Ok(status_page) = super::StatusPage::open(true) {
+        let options = &[(selinux_sys::AVC_OPT_SETENFORCE, std::ptr::null())];
+        let avc = crate::avc::AccessVectorCache::initialize(options).unwrap();
+        drop(avc);
+
+        assert_eq!(status_page.current_mode().unwrap(), crate::current_mode());
+        let _seqno = status_page.policy_load_sequence_number().unwrap();
+    }
+}
+
+#[serial]
+#[test]
+fn status_page_updated_concurrently() {
+    if let Ok(status_page) = super::StatusPage::open(true) {
+        let options = &[(selinux_sys::AVC_OPT_SETENFORCE, std::ptr::null())];
+        let avc = crate::avc::AccessVectorCache::initialize(options).unwrap();
+
+        std::thread::scope(|scope| {
+            for _ in 0..4_u8 {
+                scope.spawn(|| {
+                    for _ in 0..100_u8 {
+                        status_page.updated().unwrap();
+                        avc.reset().unwrap();
+                    }
+                });
+            }
+        });
+    }
+}
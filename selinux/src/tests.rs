@@ -304,6 +304,45 @@ fn security_context_of_se_user_with_selected_context() {
     //    super::SecurityContext::of_se_user_with_selected_context("unconfined_u", false).unwrap();
 }
 
+#[test]
+fn security_context_of_se_user_with_answers() {
+    let level = match super::kernel_support() {
+        super::KernelSupport::SELinuxMLS => Some("s0"),
+        _ => None,
+    };
+
+    let context = super::SecurityContext::of_se_user_with_answers(
+        "unconfined_u",
+        "unconfined_r",
+        None,
+        level,
+        false,
+    )
+    .unwrap();
+    let parsed = super::ParsedContext::try_from(&context).unwrap();
+    assert_eq!(parsed.role, "unconfined_r");
+    assert_eq!(parsed.the_type, "unconfined_t");
+
+    let _context = super::SecurityContext::of_se_user_with_answers(
+        "unconfined_u",
+        "unconfined_r",
+        Some("unconfined_t"),
+        level,
+        true,
+    )
+    .unwrap();
+
+    let err = super::SecurityContext::of_se_user_with_answers(
+        "unconfined_u",
+        "unconfined_r",
+        Some("invalid_t"),
+        level,
+        false,
+    )
+    .unwrap_err();
+    assert_matches!(err, super::errors::Error::IO1Name { .. });
+}
+
 #[test]
 fn security_context_default_for_se_user() {
     let _context =
@@ -429,6 +468,28 @@ fn security_context_query_access_decision() {
     let _new_context = raw_context
         .query_access_decision(&raw_context, target_class, 0)
         .unwrap();
+
+    let requested = target_class.access_vector_bit("getattr").unwrap()
+        | target_class.access_vector_bit("setexec").unwrap();
+    let decision = context
+        .query_access_decision(&context, target_class, requested)
+        .unwrap();
+    assert_eq!(decision.class(), target_class);
+    assert_eq!(decision.requested(), requested);
+    assert_eq!(decision.denied() & decision.allowed(), 0);
+    assert_eq!(decision.is_allowed(requested), decision.denied() == 0);
+
+    let allowed_names = decision.allowed_permission_names().unwrap();
+    let denied_names = decision.denied_permission_names().unwrap();
+    assert_eq!(allowed_names.len() + denied_names.len(), 2);
+    for name in allowed_names.iter().chain(&denied_names) {
+        assert!(name == "getattr" || name == "setexec");
+    }
+
+    let _ignored = decision.should_audit(decision.denied() == 0);
+    let _ignored = decision.is_permissive_domain();
+    let _ignored = decision.policy_seqno();
+    let _ignored = selinux_sys::av_decision::from(&decision);
 }
 
 #[test]
@@ -777,6 +838,75 @@ fn dynamic_mapping_into_native_form() {
     assert!(c_map[2].name.is_null());
 }
 
+#[test]
+fn security_context_session_for_se_user() {
+    let context = super::SecurityContext::session_for_se_user(
+        "unconfined_u",
+        "unconfined_r",
+        None,
+        None,
+        false,
+    )
+    .unwrap();
+    assert_eq!(
+        super::ParsedContext::try_from(&context).unwrap().role,
+        "unconfined_r"
+    );
+
+    let context = super::SecurityContext::session_for_se_user(
+        "unconfined_u",
+        "unconfined_r",
+        Some("s0"),
+        None,
+        true,
+    )
+    .unwrap();
+    assert!(context.is_raw_format());
+
+    // Roles without a configured context fall back to their default type.
+    let err =
+        super::SecurityContext::session_for_se_user("unconfined_u", "invalid_r", None, None, false)
+            .unwrap_err();
+    assert_matches!(
+        err,
+        crate::errors::Error::IO {
+            operation: "get_default_type()",
+            ..
+        }
+    );
+
+    let se_list = super::SecurityContextList::of_se_user("unconfined_u", None, None).unwrap();
+    let current = super::SecurityContext::current(true).unwrap();
+    let reachable = super::SecurityContextList::of_computed_user("unconfined_u", &current).unwrap();
+
+    for role in ["system_r", "sysadm_r", "staff_r", "user_r"] {
+        let selection = super::ContextSelection::Role(role);
+        if se_list.selected_context(selection).unwrap().is_some() {
+            continue;
+        }
+
+        match super::SecurityContext::session_for_se_user("unconfined_u", role, None, None, true) {
+            Ok(context) => {
+                assert_eq!(super::ParsedContext::try_from(&context).unwrap().role, role);
+                assert!(reachable
+                    .iter()
+                    .any(|reachable| reachable.as_bytes() == context.as_bytes()));
+            }
+
+            Err(err) => assert_matches!(
+                err,
+                crate::errors::Error::IO {
+                    operation: "get_default_type()" | "get_default_context()",
+                    ..
+                } | crate::errors::Error::IO1Name {
+                    operation: "security_compute_user()" | "security_check_context()",
+                    ..
+                }
+            ),
+        }
+    }
+}
+
 #[test]
 fn security_context_list_of_se_user() {
     let mut se_list = super::SecurityContextList::of_se_user("unconfined_u", None, None).unwrap();
@@ -799,6 +929,110 @@ fn security_context_list_of_se_user() {
         super::SecurityContextList::of_se_user("unconfined_u", None, Some(&context)).unwrap();
 }
 
+#[test]
+fn security_context_list_iter() {
+    let se_list = super::SecurityContextList::of_se_user("unconfined_u", None, None).unwrap();
+    assert!(!se_list.is_raw_format());
+
+    let iter = se_list.iter();
+    assert_eq!(iter.len(), se_list.len());
+    assert_eq!(iter.clone().count(), se_list.len());
+    assert!(iter.clone().all(|context| !context.is_raw_format()));
+
+    let forward: Vec<_> = (&se_list)
+        .into_iter()
+        .map(|c| c.to_c_string().unwrap().unwrap().into_owned())
+        .collect();
+    let mut backward: Vec<_> = se_list
+        .iter()
+        .rev()
+        .map(|c| c.to_c_string().unwrap().unwrap().into_owned())
+        .collect();
+    backward.reverse();
+    assert_eq!(forward, backward);
+
+    let mut iter = se_list.iter();
+    while iter.next().is_some() {}
+    assert!(iter.next().is_none());
+    assert!(iter.next_back().is_none());
+    assert_eq!(iter.len(), 0);
+
+    let owned = se_list.to_vec().unwrap();
+    assert_eq!(owned.len(), se_list.len());
+    for (owned, context) in owned.iter().zip(&se_list) {
+        assert_eq!(
+            owned.as_c_str(),
+            context.to_c_string().unwrap().unwrap().as_ref()
+        );
+    }
+
+    let context = super::SecurityContext::current(true).unwrap();
+    let se_list = super::SecurityContextList::of_computed_user("unconfined_u", &context).unwrap();
+    assert!(se_list.is_raw_format());
+    assert!(se_list
+        .to_vec()
+        .unwrap()
+        .iter()
+        .all(super::OwnedSecurityContext::is_raw_format));
+}
+
+#[test]
+fn security_context_list_selected_context() {
+    let se_list = super::SecurityContextList::of_se_user("unconfined_u", None, None).unwrap();
+
+    let first = se_list
+        .selected_context(super::ContextSelection::First)
+        .unwrap()
+        .unwrap();
+    assert_eq!(first.as_bytes(), se_list.get(0, false).unwrap().as_bytes());
+
+    let context = se_list
+        .selected_context(super::ContextSelection::Role("unconfined_r"))
+        .unwrap()
+        .unwrap();
+    let parsed = super::ParsedContext::try_from(&context).unwrap();
+    assert_eq!(parsed.role, "unconfined_r");
+
+    // Levels are compared in raw format.
+    let raw_parsed = if context.is_raw_format() {
+        parsed
+    } else {
+        super::ParsedContext::try_from(&context.to_raw_format().unwrap()).unwrap()
+    };
+
+    if let Some(super::ContextRange::Mls(range)) = &raw_parsed.range {
+        let selection = super::ContextSelection::Level(range.low());
+        assert!(se_list.selected_context(selection).unwrap().is_some());
+    }
+
+    let selection = super::ContextSelection::Role("invalid_r");
+    assert!(se_list.selected_context(selection).unwrap().is_none());
+
+    let mut visited = 0_usize;
+    let is_last: &mut dyn FnMut(&super::SecurityContext) -> bool = &mut |_context| {
+        visited += 1;
+        visited == se_list.len()
+    };
+    let selection = super::ContextSelection::Custom(is_last);
+    let _ignored = format!("{:?}", &selection);
+    assert!(se_list.selected_context(selection).unwrap().is_some());
+    assert_eq!(visited, se_list.len());
+}
+
+#[test]
+fn security_context_list_of_computed_user() {
+    for raw_format in [false, true] {
+        let context = super::SecurityContext::current(raw_format).unwrap();
+        let se_list =
+            super::SecurityContextList::of_computed_user("unconfined_u", &context).unwrap();
+        assert!(!se_list.is_empty());
+        assert!(se_list.get(se_list.len(), raw_format).is_none());
+        let _context = se_list.get(se_list.len() - 1, raw_format).unwrap();
+
+        super::SecurityContextList::of_computed_user("invalid", &context).unwrap_err();
+    }
+}
+
 #[test]
 fn set_current_mode() {
     super::set_current_mode(super::SELinuxMode::NotRunning).unwrap_err();
@@ -830,3 +1064,219 @@ fn set_dynamic_mapping() {
     super::set_dynamic_mapping(&[] as &[(&str, &[&str])]).unwrap();
     super::set_dynamic_mapping(&[("file", &["read", "write"] as &[&str])]).unwrap();
 }
+
+#[test]
+fn parsed_context() {
+    let context: super::ParsedContext = "system_u:system_r:init_t:s0-s15:c0.c1023".parse().unwrap();
+    assert_eq!(context.user, "system_u");
+    assert_eq!(context.role, "system_r");
+    assert_eq!(context.the_type, "init_t");
+    let range: crate::mls::MlsRange = "s0-s15:c0.c1023".parse().unwrap();
+    assert_eq!(context.range, Some(super::ContextRange::Mls(range)));
+    assert_eq!(
+        context.to_string(),
+        "system_u:system_r:init_t:s0-s15:c0.c1023"
+    );
+
+    let context: super::ParsedContext = "user_u:user_r:user_t".parse().unwrap();
+    assert_eq!(context.range, None);
+    assert_eq!(context.to_string(), "user_u:user_r:user_t");
+
+    let mut context: super::ParsedContext =
+        "user_u:user_r:user_t:SystemLow-SystemHigh".parse().unwrap();
+    assert_eq!(
+        context.range,
+        Some(super::ContextRange::Other("SystemLow-SystemHigh".into()))
+    );
+    assert_eq!(
+        context.to_string(),
+        "user_u:user_r:user_t:SystemLow-SystemHigh"
+    );
+
+    context.range = Some(super::ContextRange::from("s0:c1,c2"));
+    assert_matches!(context.range, Some(super::ContextRange::Mls(_)));
+    assert_eq!(context.to_string(), "user_u:user_r:user_t:s0:c1.c2");
+
+    for invalid in [
+        "",
+        "user_u",
+        "user_u:user_r",
+        "user_u::user_t",
+        "user_u:user_r:user_t:",
+    ] {
+        let err = invalid.parse::<super::ParsedContext>().unwrap_err();
+        assert_matches!(err, crate::errors::Error::IO1Name { .. });
+    }
+
+    let c_context = CStr::from_bytes_with_nul(b"unconfined_u:object_r:user_tmp_t:s0\0").unwrap();
+    let se_context = super::SecurityContext::from_c_str(c_context, false);
+    let context = super::ParsedContext::try_from(&se_context).unwrap();
+    assert_eq!(context.the_type, "user_tmp_t");
+    assert_eq!(context.range.as_ref().unwrap().to_string(), "s0");
+
+    let se_context = context.to_security_context(true).unwrap();
+    assert!(se_context.is_raw_format());
+    assert_eq!(se_context.as_bytes(), c_context.to_bytes());
+
+    let opaque_context = context.to_opaque_security_context().unwrap();
+    assert_eq!(opaque_context.the_type().unwrap().as_bytes(), b"user_tmp_t");
+    assert_eq!(
+        super::ParsedContext::try_from(&opaque_context).unwrap(),
+        context
+    );
+}
+
+#[test]
+fn owned_security_context() {
+    fn assert_send_and_sync<T: Send + Sync>() {}
+    assert_send_and_sync::<super::OwnedSecurityContext>();
+
+    let context: super::OwnedSecurityContext =
+        "unconfined_u:object_r:user_tmp_t:s0".parse().unwrap();
+    assert!(!context.is_raw_format());
+    assert_eq!(context.as_bytes(), b"unconfined_u:object_r:user_tmp_t:s0");
+    assert_eq!(context.to_string(), "unconfined_u:object_r:user_tmp_t:s0");
+
+    let se_context = context.as_security_context();
+    assert!(!se_context.is_raw_format());
+    assert_eq!(se_context.as_bytes(), context.as_bytes());
+
+    let copy = super::OwnedSecurityContext::try_from(&se_context).unwrap();
+    assert_eq!(copy, context);
+
+    let mut set = HashSet::new();
+    assert!(set.insert(context.clone()));
+    assert!(!set.insert(copy));
+
+    let raw = super::OwnedSecurityContext::new(context.clone().into_c_string(), true);
+    assert!(raw.is_raw_format());
+    assert_ne!(raw, context);
+    assert_eq!(raw.as_c_str(), context.as_c_str());
+
+    "".parse::<super::OwnedSecurityContext>().unwrap_err();
+    "a\0b".parse::<super::OwnedSecurityContext>().unwrap_err();
+
+    if let Ok(se_context) = super::SecurityContext::current(false) {
+        let context = super::OwnedSecurityContext::try_from(&se_context).unwrap();
+        assert_eq!(context.as_bytes(), se_context.as_bytes());
+
+        let raw_context = context.to_raw_format().unwrap();
+        assert!(raw_context.is_raw_format());
+        let _ignored = raw_context.to_translated_format().unwrap();
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde() {
+    let context: super::OwnedSecurityContext =
+        "unconfined_u:object_r:user_tmp_t:s0".parse().unwrap();
+    let json = serde_json::to_string(&context).unwrap();
+    assert_eq!(
+        json,
+        r#"{"context":"unconfined_u:object_r:user_tmp_t:s0","raw":false}"#
+    );
+    assert_eq!(
+        serde_json::to_string(&context.as_security_context()).unwrap(),
+        json
+    );
+
+    let copy: super::OwnedSecurityContext = serde_json::from_str(&json).unwrap();
+    assert_eq!(copy, context);
+    serde_json::from_str::<super::OwnedSecurityContext>(r#"{"context":"a"}"#).unwrap_err();
+
+    let colors = super::SecurityContextColors::default();
+    let json = serde_json::to_string(&colors).unwrap();
+    assert_eq!(
+        serde_json::from_str::<super::SecurityContextColors>(&json).unwrap(),
+        colors
+    );
+
+    let parsed: super::ParsedContext = "user_u:user_r:user_t:s0".parse().unwrap();
+    let json = serde_json::to_string(&parsed).unwrap();
+    assert_eq!(
+        serde_json::from_str::<super::ParsedContext>(&json).unwrap(),
+        parsed
+    );
+
+    let class = super::SecurityClass::from_name("process").unwrap();
+    let json = serde_json::to_string(&class).unwrap();
+    assert_eq!(json, r#""process""#);
+    assert_eq!(
+        serde_json::from_str::<super::SecurityClass>(&json).unwrap(),
+        class
+    );
+    serde_json::from_str::<super::SecurityClass>(r#""no_such_class""#).unwrap_err();
+    serde_json::from_str::<super::SecurityClass>(&class.value().to_string()).unwrap_err();
+
+    let json = serde_json::to_string(&super::SELinuxMode::Enforcing).unwrap();
+    assert_eq!(json, r#""Enforcing""#);
+    let mode: super::SELinuxMode = serde_json::from_str(&json).unwrap();
+    assert_eq!(mode, super::SELinuxMode::Enforcing);
+
+    let _ignored = serde_json::to_string(&super::KernelSupport::SELinux).unwrap();
+    let _ignored = serde_json::to_string(&super::UndefinedHandling::Allowed).unwrap();
+    let _ignored =
+        serde_json::to_string(&super::ProtectionCheckingMode::CheckingRequestedProtection).unwrap();
+
+    let flags = crate::context_restore::RestoreFlags::VERBOSE
+        | crate::context_restore::RestoreFlags::RECURSE;
+    let json = serde_json::to_string(&flags).unwrap();
+    let copy: crate::context_restore::RestoreFlags = serde_json::from_str(&json).unwrap();
+    assert_eq!(copy.bits(), flags.bits());
+}
+
+crate::security_class! {
+    /// Permissions of the `file` security class.
+    struct FilePermissions: "file" {
+        /// Read the file.
+        const READ = "read";
+        /// Write the file.
+        const WRITE = "write";
+        /// Get the attributes of the file.
+        const GET_ATTRIBUTES = "getattr";
+    }
+}
+
+#[test]
+fn security_class_macro() {
+    assert_eq!(FilePermissions::CLASS_NAME, "file");
+    assert_eq!(
+        FilePermissions::PERMISSION_NAMES,
+        &["read", "write", "getattr"]
+    );
+    assert_eq!(FilePermissions::MAPPING.0, "file");
+    assert_eq!(
+        FilePermissions::MAPPING.1,
+        FilePermissions::PERMISSION_NAMES
+    );
+
+    assert_eq!(FilePermissions::READ.bits(), 1);
+    assert_eq!(FilePermissions::WRITE.bits(), 2);
+    assert_eq!(FilePermissions::GET_ATTRIBUTES.bits(), 4);
+
+    let permissions = FilePermissions::READ | FilePermissions::GET_ATTRIBUTES;
+    assert_eq!(selinux_sys::access_vector_t::from(permissions), 5);
+
+    let _class = FilePermissions::security_class().unwrap();
+    let _set_dynamic_mapping: fn() -> crate::errors::Result<()> =
+        FilePermissions::set_dynamic_mapping;
+
+    assert_eq!(super::__private::permission_index(&["a", "bc"], "bc"), 1);
+}
+
+#[test]
+fn security_context_initial_kernel_contexts() {
+    for raw_format in [false, true] {
+        match super::SecurityContext::initial_kernel_contexts(raw_format) {
+            Ok(contexts) => {
+                let contexts = contexts.collect::<crate::errors::Result<Vec<_>>>().unwrap();
+                let (_name, context) = contexts.iter().find(|(name, _)| name == "kernel").unwrap();
+                assert_eq!(context.is_raw_format(), raw_format);
+                assert!(!context.as_bytes().is_empty());
+            }
+
+            Err(_err) => assert_eq!(super::current_mode(), super::SELinuxMode::NotRunning),
+        }
+    }
+}